@@ -0,0 +1,160 @@
+//! Tests every day shares beyond its worked examples: that malformed input is
+//! rejected, that no input makes it panic, and that generated input solves.
+//!
+//! Each macro generates one test in the module it's called from:
+//!
+//! ```rust,ignore
+//! #[cfg(test)]
+//! mod tests {
+//!     use super::Day07;
+//!
+//!     aoc::generate_tests!(Day07, [1, 10, 100]);
+//!     aoc::rejects_input!(Day07, ["", "16,,1", "16,x"]);
+//!     aoc::parse_never_panics!(Day07, "\\PC*|[0-9]{1,4}(,[0-9]{1,4}){0,20}");
+//! }
+//! ```
+use crate::Solution;
+
+/// Generate a `#[test]` named `test_generate` that solves input generated for
+/// the given [Solution] at each scale, seeding the RNG with the scale's
+/// position in the list. Without a list, solves three seeds at scale 1.
+#[macro_export]
+macro_rules! generate_tests {
+    ($solution:ty) => {
+        $crate::generate_tests!($solution, [1, 1, 1]);
+    };
+    ($solution:ty, [$($scale:expr),+ $(,)?]) => {
+        #[test]
+        fn test_generate() {
+            for (seed, scale) in (0..).zip([$($scale),+]) {
+                let input = <$solution as $crate::Generate>::generate(
+                    &mut $crate::generate::rng(seed),
+                    scale,
+                );
+                if let Err(e) = $crate::checks::solve::<$solution>(&input) {
+                    panic!("seed {}, scale {}: {}", seed, scale, e);
+                }
+            }
+        }
+    };
+}
+
+/// Generate a `#[test]` named `test_malformed_input` that checks each input
+/// is rejected, either by parsing or by one of the parts.
+#[macro_export]
+macro_rules! rejects_input {
+    ($solution:ty, [$($input:expr),+ $(,)?]) => {
+        #[test]
+        fn test_malformed_input() {
+            for input in [$($input),+] {
+                assert!(
+                    $crate::checks::solve::<$solution>(input).is_err(),
+                    "{:?} should be rejected",
+                    input
+                );
+            }
+        }
+    };
+}
+
+/// Generate a proptest named `test_parse_never_panics` that runs every input
+/// from `strategy` through [check](crate::checks::check). The calling crate
+/// needs `proptest` as a dev-dependency.
+#[macro_export]
+macro_rules! parse_never_panics {
+    ($solution:ty, $strategy:expr $(,)?) => {
+        ::proptest::proptest! {
+            #[test]
+            fn test_parse_never_panics(s in $strategy) {
+                $crate::checks::check::<$solution>(&s).map_err(
+                    ::proptest::test_runner::TestCaseError::fail,
+                )?;
+            }
+        }
+    };
+}
+
+/// Parse `input` and solve both parts, as the runner does for days that don't
+/// stream.
+pub fn solve<S: Solution>(input: &str) -> crate::Result<(S::Part1, S::Part2)> {
+    let input = S::parse(input)?;
+    Ok((S::part1(&input)?, S::part2(&input)?))
+}
+
+/// Solve `input`, ignoring whether it's valid. Days that solve as they read
+/// must also give the same answers, or fail, when streamed through
+/// [Solution::solve]; a mismatch is described in the error.
+pub fn check<S: Solution>(input: &str) -> Result<(), String> {
+    let answers = |result: crate::Result<(S::Part1, S::Part2)>| {
+        result
+            .map(|(part1, part2)| (part1.to_string(), part2.to_string()))
+            .ok()
+    };
+    let parsed = answers(solve::<S>(input));
+    if S::STREAMING {
+        let streamed = answers(S::solve(input.as_bytes()));
+        if streamed != parsed {
+            return Err(format!(
+                "streamed answers {:?} differ from {:?}",
+                streamed, parsed
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+    use std::io::BufRead;
+
+    /// Counts lines, but only reads the first one when streamed.
+    struct Lines;
+
+    impl Solution for Lines {
+        type Input = usize;
+        type Part1 = usize;
+        type Part2 = usize;
+
+        const DAY: u8 = 0;
+        const STREAMING: bool = true;
+
+        fn parse(input: &str) -> crate::Result<Self::Input> {
+            match input.lines().count() {
+                0 => Err(Error::invalid_input("no lines")),
+                count => Ok(count),
+            }
+        }
+
+        fn part1(input: &Self::Input) -> crate::Result<Self::Part1> {
+            Ok(*input)
+        }
+
+        fn part2(input: &Self::Input) -> crate::Result<Self::Part2> {
+            Ok(*input)
+        }
+
+        fn solve(
+            mut reader: impl BufRead,
+        ) -> crate::Result<(Self::Part1, Self::Part2)> {
+            let mut line = String::new();
+            reader.read_line(&mut line)?;
+            Self::parse(&line).map(|count| (count, count))
+        }
+    }
+
+    #[test]
+    fn test_check() {
+        assert_eq!(solve::<Lines>("a\nb\n").unwrap(), (2, 2));
+        assert!(solve::<Lines>("").is_err());
+
+        assert_eq!(check::<Lines>(""), Ok(()));
+        assert_eq!(check::<Lines>("a\n"), Ok(()));
+        let expected = r#"Some(("1", "1")) differ from Some(("2", "2"))"#;
+        assert_eq!(
+            check::<Lines>("a\nb\n"),
+            Err(format!("streamed answers {}", expected))
+        );
+    }
+}
@@ -0,0 +1,152 @@
+//! The error type returned by every day, so that failures are reported the
+//! same way regardless of which day produced them.
+use std::{
+    error::Error as StdError,
+    io,
+    num::{ParseIntError, TryFromIntError},
+};
+
+use thiserror::Error;
+
+/// Any error that can be converted into a boxed [std::error::Error], e.g. the
+/// `Err` type of a [FromStr](std::str::FromStr) impl.
+pub type BoxError = Box<dyn StdError + Send + Sync + 'static>;
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    /// Input couldn't be parsed; `context` says what was being parsed (and
+    /// where, if known).
+    #[error("unable to parse {context}")]
+    Parse {
+        context: String,
+        #[source]
+        source: BoxError,
+    },
+
+    /// Input parsed fine but doesn't make sense for the puzzle.
+    #[error("invalid input: {0}")]
+    InvalidInput(String),
+
+    /// Input was valid but there is no answer for it.
+    #[error("no solution: {0}")]
+    NoSolution(String),
+
+    #[error("{context}")]
+    Io {
+        context: String,
+        #[source]
+        source: io::Error,
+    },
+}
+
+impl Error {
+    pub fn parse(
+        context: impl Into<String>,
+        source: impl Into<BoxError>,
+    ) -> Self {
+        Self::Parse {
+            context: context.into(),
+            source: source.into(),
+        }
+    }
+
+    pub fn invalid_input(msg: impl Into<String>) -> Self {
+        Self::InvalidInput(msg.into())
+    }
+
+    pub fn no_solution(msg: impl Into<String>) -> Self {
+        Self::NoSolution(msg.into())
+    }
+
+    pub fn io(context: impl Into<String>, source: io::Error) -> Self {
+        Self::Io {
+            context: context.into(),
+            source,
+        }
+    }
+
+    /// Process exit code: 2 for bad input, 3 for no solution, and 1 for
+    /// anything else.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Self::Parse { .. } | Self::InvalidInput(_) => 2,
+            Self::NoSolution(_) => 3,
+            Self::Io { .. } => 1,
+        }
+    }
+}
+
+impl From<ParseIntError> for Error {
+    fn from(e: ParseIntError) -> Self {
+        Self::parse("integer", e)
+    }
+}
+
+impl From<TryFromIntError> for Error {
+    fn from(e: TryFromIntError) -> Self {
+        Self::parse("integer", e)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Self::io("I/O error", e)
+    }
+}
+
+/// Attach context to any parse failure, e.g. which line it happened on.
+pub trait ParseContext<T> {
+    fn parse_context<C, F>(self, context: F) -> Result<T>
+    where
+        C: Into<String>,
+        F: FnOnce() -> C;
+}
+
+impl<T, E: Into<BoxError>> ParseContext<T> for std::result::Result<T, E> {
+    fn parse_context<C, F>(self, context: F) -> Result<T>
+    where
+        C: Into<String>,
+        F: FnOnce() -> C,
+    {
+        self.map_err(|e| Error::parse(context(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let err = "x".parse::<u8>().parse_context(|| "line 3").unwrap_err();
+        assert_eq!(err.to_string(), "unable to parse line 3");
+        assert_eq!(
+            err.source().unwrap().to_string(),
+            "invalid digit found in string"
+        );
+        assert_eq!(
+            Error::invalid_input("ragged rows").to_string(),
+            "invalid input: ragged rows"
+        );
+        assert_eq!(
+            Error::no_solution("no winner").to_string(),
+            "no solution: no winner"
+        );
+    }
+
+    #[test]
+    fn test_exit_code() {
+        let parse: Error = "x".parse::<u8>().unwrap_err().into();
+        let io: Error = io::Error::from(io::ErrorKind::NotFound).into();
+        for (err, code) in [
+            (parse, 2),
+            (Error::invalid_input(""), 2),
+            (Error::no_solution(""), 3),
+            (io, 1),
+        ] {
+            assert_eq!(err.exit_code(), code);
+        }
+    }
+}
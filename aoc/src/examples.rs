@@ -0,0 +1,355 @@
+//! Tests driven by the worked examples from each puzzle.
+//!
+//! Examples live in a day's `examples` directory, one `*.txt` file each. The
+//! expected answers go either in a sidecar file with the same name and an
+//! `.answers` extension, which keeps the example byte-for-byte as given in the
+//! puzzle, or in a header of `#` lines at the top of the example itself:
+//!
+//! ```text
+//! # part1: 7
+//! # part2: 5
+//! 199
+//! 200
+//! ```
+//!
+//! Either part can be left out if the puzzle doesn't give an answer for it.
+//! [example_tests!](crate::example_tests) generates a test that runs every
+//! example through a [Solution].
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{Error, Solution};
+
+/// Name of the directory next to a day's manifest that holds its examples.
+pub const EXAMPLES_DIR: &str = "examples";
+
+/// Extension of the sidecar file holding an example's expected answers.
+pub const ANSWERS_EXTENSION: &str = "answers";
+
+/// Generate a `#[test]` named `examples` that checks every example in the
+/// *caller's* `examples` directory against the given [Solution], and fails
+/// with every mismatch if any answer is wrong.
+///
+/// ```rust,ignore
+/// #[cfg(test)]
+/// mod tests {
+///     aoc::example_tests!(super::Day01);
+/// }
+/// ```
+#[macro_export]
+macro_rules! example_tests {
+    ($solution:ty) => {
+        #[test]
+        fn examples() {
+            let dir = ::std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join($crate::examples::EXAMPLES_DIR);
+            let failures = $crate::examples::check::<$solution>(&dir).unwrap();
+            assert!(failures.is_empty(), "{}", failures.join("\n"));
+        }
+    };
+}
+
+/// A puzzle input along with the answers it should give.
+#[derive(Debug, Default, PartialEq)]
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Example {
+    /// Read the expected answers from `sidecar` if given, otherwise from the
+    /// header of `contents`.
+    pub fn parse(
+        name: impl Into<String>,
+        contents: &str,
+        sidecar: Option<&str>,
+    ) -> crate::Result<Self> {
+        let mut example = Self {
+            name: name.into(),
+            ..Default::default()
+        };
+        let input = match sidecar {
+            Some(answers) => {
+                example.set_answers(answers.lines())?;
+                contents
+            }
+            None => {
+                let mut header = Vec::new();
+                let mut rest = contents;
+                while let Some(line) = rest.strip_prefix('#') {
+                    let (line, tail) =
+                        line.split_once('\n').unwrap_or((line, ""));
+                    header.push(line);
+                    rest = tail;
+                }
+                example.set_answers(header.into_iter())?;
+                rest
+            }
+        };
+        if example.part1.is_none() && example.part2.is_none() {
+            return Err(Error::invalid_input(format!(
+                "example {} has no expected answers",
+                example.name
+            )));
+        }
+        example.input = input.to_string();
+        Ok(example)
+    }
+
+    fn set_answers<'a>(
+        &mut self,
+        lines: impl Iterator<Item = &'a str>,
+    ) -> crate::Result<()> {
+        for line in lines.map(str::trim).filter(|line| !line.is_empty()) {
+            let context = || format!("answers for example {}", self.name);
+            let (key, answer) = line.split_once(':').ok_or_else(|| {
+                Error::parse(context(), format!("{:?}", line))
+            })?;
+            let answer = Some(answer.trim().to_string());
+            match key.trim() {
+                "part1" => self.part1 = answer,
+                "part2" => self.part2 = answer,
+                key => {
+                    return Err(Error::parse(
+                        context(),
+                        format!("unknown part {:?}", key),
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Load every `*.txt` example in `dir`, sorted by name.
+pub fn load(dir: &Path) -> crate::Result<Vec<Example>> {
+    let read = |path: &Path| {
+        fs::read_to_string(path).map_err(|e| {
+            Error::io(format!("unable to read {}", path.display()), e)
+        })
+    };
+    let entries = fs::read_dir(dir).map_err(|e| {
+        Error::io(format!("unable to read {}", dir.display()), e)
+    })?;
+    let mut paths = entries
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<PathBuf>, _>>()
+        .map_err(|e| {
+            Error::io(format!("unable to read {}", dir.display()), e)
+        })?;
+    paths.retain(|path| path.extension().is_some_and(|ext| ext == "txt"));
+    paths.sort();
+
+    paths
+        .iter()
+        .map(|path| {
+            let name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy())
+                .unwrap_or_default();
+            let sidecar = path.with_extension(ANSWERS_EXTENSION);
+            let sidecar = if sidecar.exists() {
+                Some(read(&sidecar)?)
+            } else {
+                None
+            };
+            Example::parse(name, &read(path)?, sidecar.as_deref())
+        })
+        .collect()
+}
+
+/// Run every example in `dir` through `S`, and describe each answer that was
+/// wrong or couldn't be found. Days that solve as they read are also checked
+/// through [Solution::solve]. Having no examples at all is an error, so that
+/// a misplaced directory doesn't pass silently.
+pub fn check<S: Solution>(dir: &Path) -> crate::Result<Vec<String>> {
+    let examples = load(dir)?;
+    if examples.is_empty() {
+        return Err(Error::invalid_input(format!(
+            "no examples in {}",
+            dir.display()
+        )));
+    }
+
+    let mut failures = Vec::new();
+    for example in &examples {
+        if S::STREAMING {
+            let name = format!("{} (streamed)", example.name);
+            match S::solve(example.input.as_bytes()) {
+                Ok((part1, part2)) => {
+                    let parts = [
+                        compare(example.part1.as_deref(), || Ok(part1)),
+                        compare(example.part2.as_deref(), || Ok(part2)),
+                    ];
+                    record(&mut failures, &name, parts);
+                }
+                Err(e) => failures.push(format!("{}: {}", name, e)),
+            }
+        }
+        let input = match S::parse(&example.input) {
+            Ok(input) => input,
+            Err(e) => {
+                failures.push(format!("{}: {}", example.name, e));
+                continue;
+            }
+        };
+        let parts = [
+            compare(example.part1.as_deref(), || S::part1(&input)),
+            compare(example.part2.as_deref(), || S::part2(&input)),
+        ];
+        record(&mut failures, &example.name, parts);
+    }
+    Ok(failures)
+}
+
+/// Add a failure for each part of example `name` that has one.
+fn record(failures: &mut Vec<String>, name: &str, parts: [Option<String>; 2]) {
+    for (part, failure) in (1..).zip(parts) {
+        if let Some(failure) = failure {
+            failures.push(format!("{} part {}: {}", name, part, failure));
+        }
+    }
+}
+
+fn compare<T: Display>(
+    expected: Option<&str>,
+    solve: impl FnOnce() -> crate::Result<T>,
+) -> Option<String> {
+    let expected = expected?;
+    match solve() {
+        Ok(answer) if answer.to_string() == expected => None,
+        Ok(answer) => Some(format!("expected {}, got {}", expected, answer)),
+        Err(e) => Some(format!("expected {}, got error: {}", expected, e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<u32>;
+        type Part1 = u32;
+        type Part2 = u32;
+
+        const DAY: u8 = 0;
+
+        fn parse(input: &str) -> crate::Result<Self::Input> {
+            crate::parse_input!(input, u32)
+        }
+
+        fn part1(input: &Self::Input) -> crate::Result<Self::Part1> {
+            Ok(input.iter().sum())
+        }
+
+        fn part2(input: &Self::Input) -> crate::Result<Self::Part2> {
+            input
+                .iter()
+                .max()
+                .copied()
+                .ok_or_else(|| Error::no_solution("no numbers"))
+        }
+    }
+
+    /// [Sum], also checked through the default [Solution::solve].
+    struct Streamed;
+
+    impl Solution for Streamed {
+        type Input = Vec<u32>;
+        type Part1 = u32;
+        type Part2 = u32;
+
+        const DAY: u8 = 0;
+        const STREAMING: bool = true;
+
+        fn parse(input: &str) -> crate::Result<Self::Input> {
+            Sum::parse(input)
+        }
+
+        fn part1(input: &Self::Input) -> crate::Result<Self::Part1> {
+            Sum::part1(input)
+        }
+
+        fn part2(input: &Self::Input) -> crate::Result<Self::Part2> {
+            Sum::part2(input)
+        }
+    }
+
+    #[test]
+    fn test_parse_header() {
+        let example =
+            Example::parse("a", "# part1: 7\n#part2 : 5 \n199\n200\n", None)
+                .unwrap();
+        let expected = Example {
+            name: "a".into(),
+            input: "199\n200\n".into(),
+            part1: Some("7".into()),
+            part2: Some("5".into()),
+        };
+        assert_eq!(example, expected);
+
+        let example = Example::parse("b", "# part2: x", None).unwrap();
+        assert_eq!((example.part1, example.part2), (None, Some("x".into())));
+        assert_eq!(example.input, "");
+    }
+
+    #[test]
+    fn test_parse_sidecar() {
+        let example =
+            Example::parse("a", "# not a header\n", Some("part1: 7\n\n"))
+                .unwrap();
+        assert_eq!(example.input, "# not a header\n");
+        assert_eq!((example.part1, example.part2), (Some("7".into()), None));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Example::parse("a", "1\n2\n", None).is_err());
+        assert!(Example::parse("a", "1", Some("")).is_err());
+        assert!(Example::parse("a", "# part3: 1\n1", None).is_err());
+        assert!(Example::parse("a", "1", Some("part1 7")).is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |name: &str, contents: &str| {
+            fs::write(dir.path().join(name), contents).unwrap()
+        };
+        assert!(check::<Sum>(dir.path()).is_err());
+
+        write("a.txt", "# part1: 6\n# part2: 3\n1\n2\n3\n");
+        write("b.txt", "4\n5\n");
+        write("b.answers", "part1: 9\npart2: 5\n");
+        write("notes.md", "not an example");
+        assert_eq!(check::<Sum>(dir.path()).unwrap(), Vec::<String>::new());
+
+        write("c.txt", "# part1: 1\n# part2: 1\n");
+        write("d.txt", "# part1: 1\nx\n");
+        assert_eq!(
+            check::<Sum>(dir.path()).unwrap(),
+            [
+                "c part 1: expected 1, got 0",
+                "c part 2: expected 1, got error: no solution: no numbers",
+                "d: unable to parse line 1 as u32: \"x\"",
+            ]
+        );
+        assert_eq!(
+            check::<Streamed>(dir.path()).unwrap(),
+            [
+                "c (streamed): no solution: no numbers",
+                "c part 1: expected 1, got 0",
+                "c part 2: expected 1, got error: no solution: no numbers",
+                "d (streamed): unable to parse line 1 as u32: \"x\"",
+                "d: unable to parse line 1 as u32: \"x\"",
+            ]
+        );
+    }
+}
@@ -1,5 +1,15 @@
+use std::{
+    any::type_name,
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+    path::Path,
+    str::FromStr,
+};
+
 pub use anyhow;
 
+use crate::{BoxError, ParseContext};
+
 /// Return a path to the input, starting at `CARGO_MANIFEST_DIR` of the
 /// *caller*, in a way that compiles the result into the final binary.
 ///
@@ -18,8 +28,63 @@ macro_rules! localpath {
     }};
 }
 
+/// Path that stands for standard input rather than a file.
+pub const STDIN: &str = "-";
+
+/// Open the input at `path` for reading, or standard input if `path` is
+/// [STDIN], so that input can be piped in.
+pub fn open_input(path: impl AsRef<Path>) -> crate::Result<Box<dyn BufRead>> {
+    let path = path.as_ref();
+    if path == Path::new(STDIN) {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+    let file = File::open(path).map_err(|e| {
+        crate::Error::io(format!("unable to read {}", path.display()), e)
+    })?;
+    Ok(Box::new(BufReader::new(file)))
+}
+
+/// Read all of the input at `path`, or standard input if `path` is [STDIN].
+pub fn read_input(path: impl AsRef<Path>) -> crate::Result<String> {
+    let path = path.as_ref();
+    let mut input = String::new();
+    open_input(path)?.read_to_string(&mut input).map_err(|e| {
+        crate::Error::io(format!("unable to read {}", path.display()), e)
+    })?;
+    Ok(input)
+}
+
+/// Lazily parse each line of `reader` as `T`, so that input can be processed
+/// in constant memory. Errors say which line failed; iteration can carry on
+/// past them, but most callers will want to stop at the first.
+/// ```rust
+/// use aoc::files::parse_lines;
+/// let mut lines = parse_lines::<u32>("42\nx\n24".as_bytes());
+/// assert_eq!(lines.next().unwrap().unwrap(), 42);
+/// assert!(lines.next().unwrap().is_err());
+/// assert_eq!(lines.next().unwrap().unwrap(), 24);
+/// assert!(lines.next().is_none());
+/// ```
+pub fn parse_lines<T>(
+    reader: impl BufRead,
+) -> impl Iterator<Item = crate::Result<T>>
+where
+    T: FromStr,
+    T::Err: Into<BoxError>,
+{
+    reader.lines().enumerate().map(|(idx, bufline)| {
+        let line = bufline.map_err(|e| {
+            crate::Error::io("error iterating over bufreader", e)
+        })?;
+        line.parse::<T>().parse_context(|| {
+            format!("line {} as {}: {:?}", idx + 1, type_name::<T>(), line)
+        })
+    })
+}
+
 /// Parse input into a vec of specified type, or default to `Vec<String>`.
-/// Test out a link to [parse_input].
+/// `$path` is read as a file if it exists, or else as the input itself. See
+/// [parse_lines] to avoid collecting everything up front.
 /// ```rust
 /// use aoc::parse_input;
 /// assert_eq!(parse_input!("42\n24", u32).unwrap(), vec![42_u32, 24]);
@@ -33,10 +98,9 @@ macro_rules! parse_input {
     ($path:expr, $ty:ty) => {{
         use ::std::boxed::Box;
         use ::std::fs::File;
+        use ::std::io::BufReader;
         use ::std::io::Read;
-        use ::std::io::{BufRead, BufReader};
         use ::std::path::PathBuf;
-        use $crate::files::anyhow;
 
         let path = PathBuf::from($path);
         let file = File::open(&path);
@@ -46,21 +110,8 @@ macro_rules! parse_input {
         } else {
             Box::new(path.to_str().unwrap().as_bytes())
         };
-        BufReader::new(input)
-            .lines()
-            .map(|bufline| {
-                anyhow::Context::context(
-                    bufline,
-                    "error iterating over bufreader",
-                )
-                .and_then(|line| {
-                    anyhow::Context::context(
-                        line.parse::<$ty>().map_err(|e| anyhow::anyhow!(e)),
-                        "Unable to parse as type",
-                    )
-                })
-            })
-            .collect::<anyhow::Result<Vec<_>>>()
+        $crate::files::parse_lines::<$ty>(BufReader::new(input))
+            .collect::<$crate::Result<Vec<_>>>()
     }};
 }
 
@@ -103,6 +154,30 @@ mod tests {
         assert_eq!(expected, result);
     }
 
+    #[test]
+    fn test_read_input() {
+        let mut tmpfile = tempfile::NamedTempFile::new().unwrap();
+        write!(tmpfile, "{}", U32_TEST).unwrap();
+        assert_eq!(read_input(tmpfile.path()).unwrap(), U32_TEST);
+
+        let err = read_input("does/not/exist.txt").unwrap_err();
+        assert_eq!(err.to_string(), "unable to read does/not/exist.txt");
+        assert_eq!(err.exit_code(), 1);
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let mut lines = parse_lines::<u32>("1\n2\nthree\n".as_bytes());
+        assert_eq!(lines.next().unwrap().unwrap(), 1);
+        assert_eq!(lines.next().unwrap().unwrap(), 2);
+        let err = lines.next().unwrap().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unable to parse line 3 as u32: \"three\""
+        );
+        assert!(lines.next().is_none());
+    }
+
     #[test]
     fn test_read_to_custom_type() {
         #[derive(Debug, PartialEq)]
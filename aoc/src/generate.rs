@@ -0,0 +1,47 @@
+//! Synthetic puzzle input, for stress testing and benchmarking a day well
+//! beyond the size of the real input.
+//!
+//! A day implements [Generate] to write random but valid input, usually from
+//! a `generate` function in that day taking more specific size parameters.
+//! The same seed always gives the same input, so a slow or failing case can
+//! be reproduced:
+//!
+//! ```console
+//! $ cargo run --release -p d05 -- generate --scale 100 --seed 7 > big.txt
+//! $ cargo run --release -p d05 -- generate --scale 100 | \
+//!     cargo run --release -p d05 -- bench -
+//! ```
+pub use rand::{self, rngs::StdRng, RngExt, SeedableRng};
+
+/// Seed used by `generate` unless `--seed` is given.
+pub const DEFAULT_SEED: u64 = 2021;
+
+/// Write random input for a day in the puzzle's format.
+pub trait Generate {
+    /// Input about `scale` times the size of the real puzzle input, so a
+    /// `scale` of 1 should be comparable to `input.txt`. Days whose input
+    /// type has a fixed size say so, and ignore `scale` where it can't apply.
+    fn generate(rng: &mut StdRng, scale: usize) -> String;
+}
+
+/// Seeded RNG for [Generate], so that output is reproducible.
+pub fn rng(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rng_is_reproducible() {
+        let sample = |seed| {
+            let mut rng = rng(seed);
+            (0..10)
+                .map(|_| rng.random_range(0..100))
+                .collect::<Vec<u32>>()
+        };
+        assert_eq!(sample(1), sample(1));
+        assert_ne!(sample(1), sample(2));
+    }
+}
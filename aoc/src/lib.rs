@@ -1 +1,11 @@
+pub mod checks;
+mod error;
+pub mod examples;
 pub mod files;
+pub mod generate;
+pub mod runner;
+
+pub use anyhow;
+pub use error::{BoxError, Error, ParseContext, Result};
+pub use generate::Generate;
+pub use runner::Solution;
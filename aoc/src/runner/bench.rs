@@ -0,0 +1,182 @@
+//! In-process benchmarking of a [Solution], timing the parse step and each
+//! part separately so that process startup and file I/O aren't included.
+use std::{
+    fmt,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use super::Solution;
+
+/// Summary statistics over a set of timings, all in nanoseconds.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Stats {
+    pub mean: f64,
+    pub std_dev: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Stats {
+    /// Returns `None` if there are no samples.
+    pub fn new(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let nanos: Vec<f64> =
+            samples.iter().map(|d| d.as_nanos() as f64).collect();
+        let count = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / count;
+        let variance =
+            nanos.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / count;
+        Some(Self {
+            mean,
+            std_dev: variance.sqrt(),
+            min: nanos.iter().copied().fold(f64::INFINITY, f64::min),
+            max: nanos.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        })
+    }
+}
+
+/// Timings for one day, as printed by `bench`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Report {
+    pub day: u8,
+    pub iterations: usize,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+/// Human readable duration from a number of nanoseconds.
+pub(crate) fn fmt_nanos(nanos: f64) -> String {
+    match nanos {
+        n if n < 1e3 => format!("{:.1} ns", n),
+        n if n < 1e6 => format!("{:.1} µs", n / 1e3),
+        n if n < 1e9 => format!("{:.1} ms", n / 1e6),
+        n => format!("{:.2} s", n / 1e9),
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "day {:02} ({} iterations)", self.day, self.iterations)?;
+        writeln!(
+            f,
+            "{:<8}{:>12} ± {:<12}{:>12} … max",
+            "", "mean", "σ", "min"
+        )?;
+        for (name, stats) in [
+            ("parse", &self.parse),
+            ("part 1", &self.part1),
+            ("part 2", &self.part2),
+        ] {
+            writeln!(
+                f,
+                "{:<8}{:>12} ± {:<12}{:>12} … {}",
+                name,
+                fmt_nanos(stats.mean),
+                fmt_nanos(stats.std_dev),
+                fmt_nanos(stats.min),
+                fmt_nanos(stats.max),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Time `f` over `iterations` runs. It is run once beforehand, untimed, both
+/// to warm up and to bail out early if it fails.
+fn time<T>(
+    iterations: usize,
+    mut f: impl FnMut() -> crate::Result<T>,
+) -> anyhow::Result<Stats> {
+    black_box(f()?);
+    let samples = (0..iterations)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f()?);
+            Ok(start.elapsed())
+        })
+        .collect::<crate::Result<Vec<_>>>()?;
+    Stats::new(&samples).context("no iterations to time")
+}
+
+/// Benchmark parsing `input` and solving each part, `iterations` times each.
+pub fn bench<S: Solution>(
+    input: &str,
+    iterations: usize,
+) -> anyhow::Result<Report> {
+    let parse = time(iterations, || S::parse(black_box(input)))?;
+    let parsed = S::parse(input)?;
+    let part1 = time(iterations, || S::part1(black_box(&parsed)))?;
+    let part2 = time(iterations, || S::part2(black_box(&parsed)))?;
+    Ok(Report {
+        day: S::DAY,
+        iterations,
+        parse,
+        part1,
+        part2,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<u32>;
+        type Part1 = u32;
+        type Part2 = u32;
+
+        const DAY: u8 = 0;
+
+        fn parse(input: &str) -> crate::Result<Self::Input> {
+            crate::parse_input!(input, u32)
+        }
+
+        fn part1(input: &Self::Input) -> crate::Result<Self::Part1> {
+            Ok(input.iter().sum())
+        }
+
+        fn part2(_: &Self::Input) -> crate::Result<Self::Part2> {
+            Err(crate::Error::no_solution("no part 2"))
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        let samples: Vec<_> =
+            [2, 4, 4, 4, 5, 5, 7, 9].map(Duration::from_nanos).into();
+        let stats = Stats::new(&samples).unwrap();
+        assert_eq!(
+            stats,
+            Stats {
+                mean: 5.0,
+                std_dev: 2.0,
+                min: 2.0,
+                max: 9.0,
+            }
+        );
+        assert_eq!(Stats::new(&[]), None);
+    }
+
+    #[test]
+    fn test_fmt_nanos() {
+        assert_eq!(fmt_nanos(12.0), "12.0 ns");
+        assert_eq!(fmt_nanos(1_500.0), "1.5 µs");
+        assert_eq!(fmt_nanos(2_400_000.0), "2.4 ms");
+        assert_eq!(fmt_nanos(3e9), "3.00 s");
+    }
+
+    #[test]
+    fn test_bench_propagates_errors() {
+        assert!(bench::<Sum>("1\n2\n3", 3).is_err());
+        assert!(bench::<Sum>("one", 3).is_err());
+    }
+}
@@ -0,0 +1,313 @@
+//! Benchmark results kept over time, so that a change can be checked against
+//! earlier runs for regressions.
+use std::{
+    fmt,
+    fs::File,
+    io::{BufReader, BufWriter, ErrorKind, Write},
+    path::Path,
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{bail, Context};
+use serde::{Deserialize, Serialize};
+
+use super::bench::{fmt_nanos, Report};
+
+/// Percent slowdown in mean time that `compare` reports as a regression if
+/// `--threshold` isn't given.
+pub const DEFAULT_THRESHOLD: f64 = 5.0;
+
+/// A single benchmark [Report] along with when and on what it was run.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Run {
+    /// Output of `git describe --always --dirty`, if available.
+    pub commit: Option<String>,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    #[serde(flatten)]
+    pub report: Report,
+}
+
+impl Run {
+    /// Stamp `report` with the current time and the commit checked out in
+    /// `dir`.
+    pub fn new(report: Report, dir: impl AsRef<Path>) -> Self {
+        let commit = Command::new("git")
+            .args(["describe", "--always", "--dirty"])
+            .current_dir(dir)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| String::from_utf8(output.stdout).ok())
+            .map(|commit| commit.trim().to_string());
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Self {
+            commit,
+            timestamp,
+            report,
+        }
+    }
+
+    fn label(&self) -> &str {
+        self.commit.as_deref().unwrap_or("unknown")
+    }
+}
+
+/// Every saved [Run] for a day, oldest first.
+#[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct History(Vec<Run>);
+
+impl History {
+    /// Load a history file, or an empty history if it doesn't exist yet.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        match File::open(path) {
+            Ok(file) => serde_json::from_reader(BufReader::new(file))
+                .with_context(|| {
+                    format!("unable to parse {}", path.display())
+                }),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e)
+                .with_context(|| format!("unable to open {}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        let mut writer =
+            BufWriter::new(File::create(path).with_context(|| {
+                format!("unable to create {}", path.display())
+            })?);
+        serde_json::to_writer_pretty(&mut writer, self)?;
+        writeln!(writer)?;
+        Ok(())
+    }
+
+    pub fn push(&mut self, run: Run) {
+        self.0.push(run)
+    }
+
+    pub fn runs(&self) -> &[Run] {
+        &self.0
+    }
+
+    /// Index of the most recent run whose commit starts with `selector`.
+    fn find(&self, selector: &str) -> anyhow::Result<usize> {
+        self.0
+            .iter()
+            .rposition(|run| {
+                run.commit
+                    .as_deref()
+                    .is_some_and(|commit| commit.starts_with(selector))
+            })
+            .with_context(|| format!("no saved run for commit {}", selector))
+    }
+
+    /// Pick two runs to compare. `new` defaults to the latest run and `base`
+    /// to the one saved just before `new`.
+    pub fn select(
+        &self,
+        base: Option<&str>,
+        new: Option<&str>,
+    ) -> anyhow::Result<(&Run, &Run)> {
+        let new_idx = match new {
+            Some(selector) => self.find(selector)?,
+            None => match self.0.len() {
+                0 => bail!("no saved benchmark runs"),
+                len => len - 1,
+            },
+        };
+        let base_idx = match base {
+            Some(selector) => self.find(selector)?,
+            None => new_idx
+                .checked_sub(1)
+                .context("need at least two saved runs to compare")?,
+        };
+        Ok((&self.0[base_idx], &self.0[new_idx]))
+    }
+}
+
+/// Change in mean time for one step (parse, part 1 or part 2) between two
+/// runs.
+#[derive(Debug, PartialEq)]
+pub struct Change {
+    pub name: &'static str,
+    pub base: f64,
+    pub new: f64,
+}
+
+impl Change {
+    /// Percent change in mean time; positive is slower.
+    pub fn percent(&self) -> f64 {
+        (self.new - self.base) / self.base * 100.0
+    }
+}
+
+/// Per-step differences between two runs of the same day.
+#[derive(Debug)]
+pub struct Comparison<'a> {
+    pub base: &'a Run,
+    pub new: &'a Run,
+    pub threshold: f64,
+    pub changes: [Change; 3],
+}
+
+impl<'a> Comparison<'a> {
+    pub fn new(
+        base: &'a Run,
+        new: &'a Run,
+        threshold: f64,
+    ) -> anyhow::Result<Self> {
+        if base.report.day != new.report.day {
+            bail!(
+                "can't compare day {} to day {}",
+                base.report.day,
+                new.report.day
+            );
+        }
+        let (b, n) = (&base.report, &new.report);
+        Ok(Self {
+            base,
+            new,
+            threshold,
+            changes: [
+                Change {
+                    name: "parse",
+                    base: b.parse.mean,
+                    new: n.parse.mean,
+                },
+                Change {
+                    name: "part 1",
+                    base: b.part1.mean,
+                    new: n.part1.mean,
+                },
+                Change {
+                    name: "part 2",
+                    base: b.part2.mean,
+                    new: n.part2.mean,
+                },
+            ],
+        })
+    }
+
+    /// Steps that got slower by more than `threshold` percent.
+    pub fn regressions(&self) -> impl Iterator<Item = &Change> {
+        self.changes
+            .iter()
+            .filter(|change| change.percent() > self.threshold)
+    }
+}
+
+impl fmt::Display for Comparison<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "day {:02}: {} → {} (threshold {}%)",
+            self.new.report.day,
+            self.base.label(),
+            self.new.label(),
+            self.threshold
+        )?;
+        for change in &self.changes {
+            let flag = if change.percent() > self.threshold {
+                "  regression"
+            } else {
+                ""
+            };
+            writeln!(
+                f,
+                "{:<8}{:>12} → {:<12}{:>+8.1}%{}",
+                change.name,
+                fmt_nanos(change.base),
+                fmt_nanos(change.new),
+                change.percent(),
+                flag
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::bench::Stats;
+
+    fn run(commit: &str, means: [f64; 3]) -> Run {
+        let stats = |mean| Stats {
+            mean,
+            std_dev: 0.0,
+            min: mean,
+            max: mean,
+        };
+        Run {
+            commit: Some(commit.to_string()),
+            timestamp: 0,
+            report: Report {
+                day: 1,
+                iterations: 1,
+                parse: stats(means[0]),
+                part1: stats(means[1]),
+                part2: stats(means[2]),
+            },
+        }
+    }
+
+    fn history() -> History {
+        History(vec![
+            run("aaaaaaa", [100.0, 100.0, 100.0]),
+            run("bbbbbbb", [100.0, 104.0, 120.0]),
+            run("ccccccc-dirty", [90.0, 100.0, 100.0]),
+        ])
+    }
+
+    #[test]
+    fn test_select() {
+        let history = history();
+        let (base, new) = history.select(None, None).unwrap();
+        assert_eq!(base.commit.as_deref(), Some("bbbbbbb"));
+        assert_eq!(new.commit.as_deref(), Some("ccccccc-dirty"));
+
+        let (base, new) = history.select(Some("a"), Some("bb")).unwrap();
+        assert_eq!(base.commit.as_deref(), Some("aaaaaaa"));
+        assert_eq!(new.commit.as_deref(), Some("bbbbbbb"));
+
+        assert!(history.select(Some("d"), None).is_err());
+        assert!(history.select(None, Some("aaa")).is_err());
+        assert!(History::default().select(None, None).is_err());
+    }
+
+    #[test]
+    fn test_regressions() {
+        let history = history();
+        let (base, new) = history.select(Some("a"), Some("b")).unwrap();
+
+        let comparison = Comparison::new(base, new, 5.0).unwrap();
+        let names: Vec<_> = comparison.regressions().map(|c| c.name).collect();
+        assert_eq!(names, ["part 2"]);
+
+        let comparison = Comparison::new(base, new, 1.0).unwrap();
+        let names: Vec<_> = comparison.regressions().map(|c| c.name).collect();
+        assert_eq!(names, ["part 1", "part 2"]);
+
+        let (base, new) = history.select(None, None).unwrap();
+        let comparison = Comparison::new(base, new, 5.0).unwrap();
+        assert_eq!(comparison.regressions().count(), 0);
+        assert_eq!(comparison.changes[0].percent(), -10.0);
+    }
+
+    #[test]
+    fn test_load_and_save() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bench.json");
+        assert_eq!(History::load(&path).unwrap(), History::default());
+
+        let history = history();
+        history.save(&path).unwrap();
+        assert_eq!(History::load(&path).unwrap(), history);
+    }
+}
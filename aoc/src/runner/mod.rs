@@ -0,0 +1,467 @@
+//! Shared entry point for each day's binary.
+//!
+//! A day implements [Solution] and hands it to [main!](crate::main), which
+//! reads the puzzle input (from stdin if the path is `-`) and either prints
+//! both answers or benchmarks the parse step and each part in-process. Days
+//! that can solve as they read are given the input as a stream, so it never
+//! has to fit in memory.
+//! Answers can also be written as JSON or CSV for scripts. Saved benchmarks
+//! are kept in a [History], and any two runs can be compared for regressions.
+//! Days that implement [Generate] can also write synthetic input.
+//!
+//! ```text
+//! dNN [INPUT|-] [--format text|json|csv]
+//! dNN bench [INPUT|-] [--iterations N] [--save]
+//! dNN compare [BASE [NEW]] [--threshold PERCENT]
+//! dNN generate [--seed N] [--scale N]
+//! ```
+use std::{
+    fmt::Display,
+    io::{BufRead, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use anyhow::{bail, Context};
+use history::{Comparison, History, Run, DEFAULT_THRESHOLD};
+use output::{Answer, Format};
+
+use crate::generate::{self, Generate, DEFAULT_SEED};
+
+pub mod bench;
+pub mod history;
+pub mod output;
+
+/// Number of timed runs for `bench` if `--iterations` isn't given.
+pub const DEFAULT_ITERATIONS: usize = 100;
+
+/// Name of the [History] file kept next to a day's manifest.
+pub const BENCH_FILENAME: &str = "bench.json";
+
+/// A day's puzzle, split into parsing and the two parts so that each step can
+/// be run (and timed) on its own.
+pub trait Solution {
+    /// Day of the month, used to label output.
+    const DAY: u8;
+
+    /// Parsed puzzle input shared by both parts.
+    type Input;
+    type Part1: Display;
+    type Part2: Display;
+
+    /// Whether [solve](Self::solve) works through its input as it reads it.
+    /// If so, the runner solves with it rather than reading all of the input
+    /// first, at the cost of timing both parts together.
+    const STREAMING: bool = false;
+
+    fn parse(input: &str) -> crate::Result<Self::Input>;
+    fn part1(input: &Self::Input) -> crate::Result<Self::Part1>;
+    fn part2(input: &Self::Input) -> crate::Result<Self::Part2>;
+
+    /// Solve both parts from `reader`. By default this reads all of the input
+    /// and runs [parse](Self::parse) and each part; days that set
+    /// [STREAMING](Self::STREAMING) override it to solve in a single pass.
+    fn solve(
+        mut reader: impl BufRead,
+    ) -> crate::Result<(Self::Part1, Self::Part2)> {
+        let mut input = String::new();
+        reader
+            .read_to_string(&mut input)
+            .map_err(|e| crate::Error::io("unable to read input", e))?;
+        let parsed = Self::parse(&input)?;
+        Ok((Self::part1(&parsed)?, Self::part2(&parsed)?))
+    }
+}
+
+/// Generate `fn main` for a day, running the given [Solution] with
+/// `input.txt` next to the *caller's* manifest as the default input. The day
+/// must also implement [Generate]. See [main](fn@main) for how failures are
+/// reported.
+///
+/// ```rust,no_run
+/// struct Day00;
+///
+/// impl aoc::Solution for Day00 {
+///     type Input = Vec<String>;
+///     type Part1 = usize;
+///     type Part2 = usize;
+///
+///     const DAY: u8 = 0;
+///
+///     fn parse(input: &str) -> aoc::Result<Self::Input> {
+///         Ok(input.lines().map(Into::into).collect())
+///     }
+///
+///     fn part1(input: &Self::Input) -> aoc::Result<Self::Part1> {
+///         Ok(input.len())
+///     }
+///
+///     fn part2(input: &Self::Input) -> aoc::Result<Self::Part2> {
+///         Ok(input.iter().map(String::len).sum())
+///     }
+/// }
+///
+/// impl aoc::Generate for Day00 {
+///     fn generate(rng: &mut aoc::generate::StdRng, scale: usize) -> String {
+///         use aoc::generate::RngExt;
+///         (0..100 * scale)
+///             .map(|_| format!("{}\n", rng.random_range(0..1000)))
+///             .collect()
+///     }
+/// }
+///
+/// aoc::main!(Day00);
+/// ```
+#[macro_export]
+macro_rules! main {
+    ($solution:ty) => {
+        fn main() -> ::std::process::ExitCode {
+            $crate::runner::main::<$solution>(env!("CARGO_MANIFEST_DIR"))
+        }
+    };
+}
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Solve {
+        format: Format,
+    },
+    Bench {
+        iterations: usize,
+        save: bool,
+    },
+    /// Base and new runs are selected by commit, see [History::select].
+    Compare {
+        base: Option<String>,
+        new: Option<String>,
+        threshold: f64,
+    },
+    Generate {
+        seed: u64,
+        scale: usize,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    command: Command,
+    input: Option<PathBuf>,
+}
+
+impl Args {
+    fn parse(args: impl IntoIterator<Item = String>) -> anyhow::Result<Self> {
+        let mut args = args.into_iter().peekable();
+        let subcommand = args.next_if(|arg| {
+            arg == "bench" || arg == "compare" || arg == "generate"
+        });
+        let (bench, compare, generate) = match subcommand.as_deref() {
+            Some("bench") => (true, false, false),
+            Some("compare") => (false, true, false),
+            Some("generate") => (false, false, true),
+            _ => (false, false, false),
+        };
+        let mut iterations = DEFAULT_ITERATIONS;
+        let mut seed = DEFAULT_SEED;
+        let mut scale = 1;
+        let mut save = false;
+        let mut threshold = DEFAULT_THRESHOLD;
+        let mut format = Format::default();
+        let mut positional = Vec::new();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--iterations" | "-n" if bench => {
+                    iterations = args
+                        .next()
+                        .context("--iterations requires a value")?
+                        .parse()
+                        .context("--iterations must be a positive integer")?;
+                    if iterations == 0 {
+                        bail!("--iterations must be a positive integer");
+                    }
+                }
+                "--save" if bench => save = true,
+                "--format" if !bench && !compare && !generate => {
+                    format = args
+                        .next()
+                        .context("--format requires a value")?
+                        .parse()?;
+                }
+                "--threshold" if compare => {
+                    threshold = args
+                        .next()
+                        .context("--threshold requires a value")?
+                        .parse()
+                        .context("--threshold must be a number")?;
+                }
+                "--seed" if generate => {
+                    seed = args
+                        .next()
+                        .context("--seed requires a value")?
+                        .parse()
+                        .context("--seed must be a non-negative integer")?;
+                }
+                "--scale" if generate => {
+                    scale = args
+                        .next()
+                        .context("--scale requires a value")?
+                        .parse()
+                        .context("--scale must be a positive integer")?;
+                    if scale == 0 {
+                        bail!("--scale must be a positive integer");
+                    }
+                }
+                flag if flag.starts_with("--") => {
+                    bail!("unrecognized option: {}", flag)
+                }
+                _ => positional.push(arg),
+            }
+        }
+
+        let max_positional = match (compare, generate) {
+            (true, _) => 2,
+            (_, true) => 0,
+            _ => 1,
+        };
+        if let Some(arg) = positional.get(max_positional) {
+            bail!("unexpected argument: {}", arg);
+        }
+        let mut positional = positional.into_iter();
+
+        Ok(if generate {
+            Self {
+                command: Command::Generate { seed, scale },
+                input: None,
+            }
+        } else if compare {
+            Self {
+                command: Command::Compare {
+                    base: positional.next(),
+                    new: positional.next(),
+                    threshold,
+                },
+                input: None,
+            }
+        } else {
+            Self {
+                command: if bench {
+                    Command::Bench { iterations, save }
+                } else {
+                    Command::Solve { format }
+                },
+                input: positional.next().map(PathBuf::from),
+            }
+        })
+    }
+}
+
+/// Run a [Solution] and report any failure, along with its causes, on
+/// stderr. The exit code is [Error::exit_code](crate::Error::exit_code) for
+/// errors from the day itself, so that scripts can tell bad input from a
+/// puzzle with no answer, and 1 for anything else.
+pub fn main<S: Solution + Generate>(manifest_dir: &str) -> ExitCode {
+    match run::<S>(manifest_dir) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            for cause in e.chain().skip(1) {
+                eprintln!("  caused by: {}", cause);
+            }
+            let code = e
+                .downcast_ref::<crate::Error>()
+                .map_or(1, crate::Error::exit_code);
+            ExitCode::from(code)
+        }
+    }
+}
+
+/// Run a [Solution] according to the command line arguments. `manifest_dir`
+/// is where `input.txt` is looked for and the benchmark [History] is kept;
+/// use [main!](crate::main) to have it filled in for you.
+pub fn run<S: Solution + Generate>(manifest_dir: &str) -> anyhow::Result<()> {
+    let args = Args::parse(std::env::args().skip(1))?;
+    let basedir = Path::new(manifest_dir);
+    let history_path = basedir.join(BENCH_FILENAME);
+    let input_path = || match &args.input {
+        Some(path) => path.clone(),
+        None => basedir.join("input.txt"),
+    };
+    let read_input = || crate::files::read_input(input_path());
+
+    match args.command {
+        Command::Solve { format } if S::STREAMING => {
+            let reader = crate::files::open_input(input_path())?;
+            let answers = Answer::both(S::DAY, || S::solve(reader))?;
+            format.write(&answers, std::io::stdout().lock())?;
+        }
+        Command::Solve { format } => {
+            let parsed = S::parse(&read_input()?)?;
+            let answers = [
+                Answer::new(S::DAY, 1, || S::part1(&parsed))?,
+                Answer::new(S::DAY, 2, || S::part2(&parsed))?,
+            ];
+            format.write(&answers, std::io::stdout().lock())?;
+        }
+        Command::Bench { iterations, save } => {
+            let report = bench::bench::<S>(&read_input()?, iterations)?;
+            print!("{}", report);
+            if save {
+                let mut history = History::load(&history_path)?;
+                history.push(Run::new(report, basedir));
+                history.save(&history_path)?;
+            }
+        }
+        Command::Compare {
+            ref base,
+            ref new,
+            threshold,
+        } => {
+            let history = History::load(&history_path)?;
+            let (base, new) =
+                history.select(base.as_deref(), new.as_deref())?;
+            let comparison = Comparison::new(base, new, threshold)?;
+            print!("{}", comparison);
+            let regressions = comparison.regressions().count();
+            if regressions > 0 {
+                bail!("{} regression(s) above {}%", regressions, threshold);
+            }
+        }
+        Command::Generate { seed, scale } => {
+            let input = S::generate(&mut generate::rng(seed), scale);
+            std::io::stdout().lock().write_all(input.as_bytes())?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> anyhow::Result<Args> {
+        Args::parse(s.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse_args_solve() {
+        let expected = Args {
+            command: Command::Solve {
+                format: Format::Text,
+            },
+            input: None,
+        };
+        assert_eq!(args("").unwrap(), expected);
+
+        let expected = Args {
+            command: Command::Solve {
+                format: Format::Text,
+            },
+            input: Some("foo.txt".into()),
+        };
+        assert_eq!(args("foo.txt").unwrap(), expected);
+
+        let expected = Args {
+            command: Command::Solve {
+                format: Format::Csv,
+            },
+            input: Some("foo.txt".into()),
+        };
+        assert_eq!(args("--format csv foo.txt").unwrap(), expected);
+
+        let expected = Args {
+            command: Command::Solve {
+                format: Format::Text,
+            },
+            input: Some("-".into()),
+        };
+        assert_eq!(args("-").unwrap(), expected);
+    }
+
+    #[test]
+    fn test_parse_args_bench() {
+        let expected = Args {
+            command: Command::Bench {
+                iterations: DEFAULT_ITERATIONS,
+                save: false,
+            },
+            input: None,
+        };
+        assert_eq!(args("bench").unwrap(), expected);
+
+        let expected = Args {
+            command: Command::Bench {
+                iterations: 5,
+                save: true,
+            },
+            input: Some("foo.txt".into()),
+        };
+        assert_eq!(args("bench foo.txt -n 5 --save").unwrap(), expected);
+    }
+
+    #[test]
+    fn test_parse_args_compare() {
+        let expected = Args {
+            command: Command::Compare {
+                base: None,
+                new: None,
+                threshold: DEFAULT_THRESHOLD,
+            },
+            input: None,
+        };
+        assert_eq!(args("compare").unwrap(), expected);
+
+        let expected = Args {
+            command: Command::Compare {
+                base: Some("abc123".into()),
+                new: Some("def456".into()),
+                threshold: 2.5,
+            },
+            input: None,
+        };
+        assert_eq!(
+            args("compare abc123 --threshold 2.5 def456").unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_parse_args_generate() {
+        let expected = Args {
+            command: Command::Generate {
+                seed: DEFAULT_SEED,
+                scale: 1,
+            },
+            input: None,
+        };
+        assert_eq!(args("generate").unwrap(), expected);
+
+        let expected = Args {
+            command: Command::Generate {
+                seed: 7,
+                scale: 100,
+            },
+            input: None,
+        };
+        assert_eq!(args("generate --scale 100 --seed 7").unwrap(), expected);
+    }
+
+    #[test]
+    fn test_parse_args_errors() {
+        assert!(args("--save").is_err());
+        assert!(args("bench --iterations").is_err());
+        assert!(args("bench --iterations 0").is_err());
+        assert!(args("bench --iterations many").is_err());
+        assert!(args("foo.txt bar.txt").is_err());
+        assert!(args("bench --threshold 5").is_err());
+        assert!(args("bench --format json").is_err());
+        assert!(args("--format").is_err());
+        assert!(args("--format xml").is_err());
+        assert!(args("compare --save").is_err());
+        assert!(args("compare --threshold").is_err());
+        assert!(args("compare a b c").is_err());
+        assert!(args("generate foo.txt").is_err());
+        assert!(args("generate --scale 0").is_err());
+        assert!(args("generate --seed -1").is_err());
+        assert!(args("bench --seed 1").is_err());
+    }
+}
@@ -0,0 +1,219 @@
+//! Formatting of a day's answers, either for humans or for scripts.
+use std::{
+    fmt::Display,
+    io::{self, Write},
+    str::FromStr,
+    time::Instant,
+};
+
+use anyhow::bail;
+use serde::Serialize;
+
+/// How answers are written, chosen with `--format`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Format {
+    /// `day 01 part 1: 1709`
+    #[default]
+    Text,
+    /// One JSON object per line, so the output of several days can simply be
+    /// concatenated.
+    Json,
+    /// A `day,part,answer,nanos` header followed by one row per part.
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "text" => Self::Text,
+            "json" => Self::Json,
+            "csv" => Self::Csv,
+            _ => bail!("unknown format {}, expected text, json or csv", s),
+        })
+    }
+}
+
+/// The answer to one part of a day, along with how long it took to solve.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Answer {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub nanos: u128,
+}
+
+impl Answer {
+    /// Time a single run of `solve`.
+    pub fn new<T: Display>(
+        day: u8,
+        part: u8,
+        solve: impl FnOnce() -> crate::Result<T>,
+    ) -> crate::Result<Self> {
+        let start = Instant::now();
+        let answer = solve()?;
+        let nanos = start.elapsed().as_nanos();
+        Ok(Self {
+            day,
+            part,
+            answer: answer.to_string(),
+            nanos,
+        })
+    }
+
+    /// Time a single run of `solve`, which answers both parts at once. Each
+    /// answer is given the whole time taken.
+    pub fn both<T: Display, U: Display>(
+        day: u8,
+        solve: impl FnOnce() -> crate::Result<(T, U)>,
+    ) -> crate::Result<[Self; 2]> {
+        let start = Instant::now();
+        let (part1, part2) = solve()?;
+        let nanos = start.elapsed().as_nanos();
+        Ok([
+            Self {
+                day,
+                part: 1,
+                answer: part1.to_string(),
+                nanos,
+            },
+            Self {
+                day,
+                part: 2,
+                answer: part2.to_string(),
+                nanos,
+            },
+        ])
+    }
+}
+
+/// Quote a CSV field if it contains anything that would otherwise break the
+/// row apart.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+impl Format {
+    pub fn write(
+        self,
+        answers: &[Answer],
+        mut writer: impl Write,
+    ) -> io::Result<()> {
+        match self {
+            Self::Text => {
+                for a in answers {
+                    writeln!(
+                        writer,
+                        "day {:02} part {}: {}",
+                        a.day, a.part, a.answer
+                    )?;
+                }
+            }
+            Self::Json => {
+                for a in answers {
+                    serde_json::to_writer(&mut writer, a)?;
+                    writeln!(writer)?;
+                }
+            }
+            Self::Csv => {
+                writeln!(writer, "day,part,answer,nanos")?;
+                for a in answers {
+                    writeln!(
+                        writer,
+                        "{},{},{},{}",
+                        a.day,
+                        a.part,
+                        csv_field(&a.answer),
+                        a.nanos
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answers() -> Vec<Answer> {
+        vec![
+            Answer {
+                day: 4,
+                part: 1,
+                answer: "11774".into(),
+                nanos: 1200,
+            },
+            Answer {
+                day: 4,
+                part: 2,
+                answer: "say \"hi\", again".into(),
+                nanos: 3400,
+            },
+        ]
+    }
+
+    fn write(format: Format) -> String {
+        let mut out = Vec::new();
+        format.write(&answers(), &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!("text".parse::<Format>().unwrap(), Format::Text);
+        assert_eq!("json".parse::<Format>().unwrap(), Format::Json);
+        assert_eq!("csv".parse::<Format>().unwrap(), Format::Csv);
+        assert!("yaml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn test_text() {
+        let expected =
+            "day 04 part 1: 11774\nday 04 part 2: say \"hi\", again\n";
+        assert_eq!(write(Format::Text), expected);
+    }
+
+    #[test]
+    fn test_json() {
+        let expected = r#"{"day":4,"part":1,"answer":"11774","nanos":1200}
+{"day":4,"part":2,"answer":"say \"hi\", again","nanos":3400}
+"#;
+        assert_eq!(write(Format::Json), expected);
+    }
+
+    #[test]
+    fn test_csv() {
+        let expected = r#"day,part,answer,nanos
+4,1,11774,1200
+4,2,"say ""hi"", again",3400
+"#;
+        assert_eq!(write(Format::Csv), expected);
+    }
+
+    #[test]
+    fn test_answer_propagates_errors() {
+        let answer = Answer::new(1, 1, || Ok(42)).unwrap();
+        assert_eq!((answer.day, answer.part), (1, 1));
+        assert_eq!(answer.answer, "42");
+        assert!(Answer::new(1, 2, || -> crate::Result<u8> {
+            Err(crate::Error::no_solution("no answer"))
+        })
+        .is_err());
+
+        let [first, second] = Answer::both(1, || Ok((42, "x"))).unwrap();
+        assert_eq!((first.part, first.answer.as_str()), (1, "42"));
+        assert_eq!((second.part, second.answer.as_str()), (2, "x"));
+        assert_eq!(first.nanos, second.nanos);
+        assert!(Answer::both(1, || -> crate::Result<(u8, u8)> {
+            Err(crate::Error::no_solution("no answer"))
+        })
+        .is_err());
+    }
+}
@@ -0,0 +1,346 @@
+use std::{
+    cmp::Ordering, collections::VecDeque, fmt, io::BufRead,
+    num::ParseIntError, str::FromStr,
+};
+
+use aoc::generate::{RngExt, StdRng};
+
+/// A line of input, which may be blank.
+struct Line(Option<u32>);
+
+impl FromStr for Line {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        Ok(Line(if s.is_empty() { None } else { Some(s.parse()?) }))
+    }
+}
+
+/// Lazily parse depth readings from `reader`, skipping blank lines.
+pub fn depths(reader: impl BufRead) -> impl Iterator<Item = aoc::Result<u32>> {
+    aoc::files::parse_lines::<Line>(reader)
+        .filter_map(|line| line.map(|Line(depth)| depth).transpose())
+}
+
+fn parse_input(input: &str) -> aoc::Result<Vec<u32>> {
+    let readings: Vec<_> =
+        depths(input.as_bytes()).collect::<aoc::Result<_>>()?;
+    if readings.is_empty() {
+        return Err(aoc::Error::invalid_input("no depth readings"));
+    }
+    Ok(readings)
+}
+
+/// Why a line of input gave no depth reading.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SkipReason {
+    Blank,
+    Invalid(ParseIntError),
+}
+
+/// A line of input that gave no depth reading, numbered from 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SkippedLine {
+    pub line: usize,
+    pub text: String,
+    pub reason: SkipReason,
+}
+
+impl fmt::Display for SkippedLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.reason {
+            SkipReason::Blank => write!(f, "line {}: blank", self.line),
+            SkipReason::Invalid(e) => {
+                write!(f, "line {}: {:?}: {}", self.line, self.text, e)
+            }
+        }
+    }
+}
+
+/// Depth readings from [parse_tolerant], along with every line that was
+/// skipped to get them.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct TolerantParse {
+    pub readings: Vec<u32>,
+    pub skipped: Vec<SkippedLine>,
+}
+
+impl TolerantParse {
+    /// Lines that weren't blank but still couldn't be read, which suggests a
+    /// corrupted file rather than stray whitespace.
+    pub fn invalid(&self) -> impl Iterator<Item = &SkippedLine> {
+        self.skipped
+            .iter()
+            .filter(|line| matches!(line.reason, SkipReason::Invalid(_)))
+    }
+}
+
+/// Parse every depth reading in `reader`, recording lines that are blank or
+/// not a depth instead of failing on them, so that a truncated or corrupted
+/// file can be told apart from a clean one. Only failing to read `reader`
+/// is an error.
+pub fn parse_tolerant(reader: impl BufRead) -> aoc::Result<TolerantParse> {
+    let mut parsed = TolerantParse::default();
+    for (idx, text) in reader.lines().enumerate() {
+        let text = text.map_err(|e| {
+            aoc::Error::io(format!("error reading line {}", idx + 1), e)
+        })?;
+        let reason = match text.parse() {
+            Ok(Line(Some(depth))) => {
+                parsed.readings.push(depth);
+                continue;
+            }
+            Ok(Line(None)) => SkipReason::Blank,
+            Err(e) => SkipReason::Invalid(e),
+        };
+        parsed.skipped.push(SkippedLine {
+            line: idx + 1,
+            text,
+            reason,
+        });
+    }
+    Ok(parsed)
+}
+
+/// How the sum of each window of readings compares to the one before it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Changes {
+    pub increases: usize,
+    pub decreases: usize,
+    pub plateaus: usize,
+}
+
+/// Compares the sums of consecutive windows of `size` readings, fed one
+/// reading at a time.
+///
+/// Consecutive windows share all but the reading that drops off the front
+/// and the one added at the back, so comparing those two is enough. Nothing
+/// is ever summed, so any readings that can be ordered work without
+/// overflow, and only the last `size` readings are kept.
+#[derive(Debug)]
+pub struct SlidingWindow<T> {
+    size: usize,
+    window: VecDeque<T>,
+    changes: Changes,
+}
+
+impl<T: Ord> SlidingWindow<T> {
+    /// A window of 0 readings always sums to nothing, so it counts nothing.
+    pub fn new(size: usize) -> Self {
+        Self {
+            size,
+            window: VecDeque::with_capacity(size),
+            changes: Changes::default(),
+        }
+    }
+
+    pub fn push(&mut self, depth: T) {
+        if self.size == 0 {
+            return;
+        }
+        if self.window.len() == self.size {
+            if let Some(dropped) = self.window.pop_front() {
+                match depth.cmp(&dropped) {
+                    Ordering::Greater => self.changes.increases += 1,
+                    Ordering::Less => self.changes.decreases += 1,
+                    Ordering::Equal => self.changes.plateaus += 1,
+                }
+            }
+        }
+        self.window.push_back(depth);
+    }
+
+    pub fn changes(&self) -> Changes {
+        self.changes
+    }
+}
+
+/// [Changes] between sums of consecutive windows of `size` readings.
+pub fn window_changes<T: Ord>(
+    depths: impl IntoIterator<Item = T>,
+    size: usize,
+) -> Changes {
+    let mut window = SlidingWindow::new(size);
+    for depth in depths {
+        window.push(depth);
+    }
+    window.changes()
+}
+
+fn part1<T: Ord>(depths: impl IntoIterator<Item = T>) -> usize {
+    window_changes(depths, 1).increases
+}
+
+fn part2<T: Ord>(depths: impl IntoIterator<Item = T>) -> usize {
+    window_changes(depths, 3).increases
+}
+
+/// Solve both parts in a single pass over `reader`, in constant memory.
+pub fn solve(reader: impl BufRead) -> aoc::Result<(usize, usize)> {
+    let (mut single, mut triple) =
+        (SlidingWindow::new(1), SlidingWindow::new(3));
+    let mut readings = 0;
+    for depth in depths(reader) {
+        let depth = depth?;
+        single.push(depth);
+        triple.push(depth);
+        readings += 1;
+    }
+    if readings == 0 {
+        return Err(aoc::Error::invalid_input("no depth readings"));
+    }
+    Ok((single.changes().increases, triple.changes().increases))
+}
+
+/// Random depth readings, drifting deeper like a real sonar sweep.
+pub fn generate(rng: &mut StdRng, readings: usize) -> String {
+    let mut depth: u32 = rng.random_range(100..200);
+    (0..readings)
+        .map(|_| {
+            depth = depth.saturating_add_signed(rng.random_range(-10..=20));
+            format!("{}\n", depth)
+        })
+        .collect()
+}
+
+pub struct Day01;
+
+impl aoc::Solution for Day01 {
+    const DAY: u8 = 1;
+    const STREAMING: bool = true;
+    type Input = Vec<u32>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> aoc::Result<Self::Part1> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> aoc::Result<Self::Part2> {
+        Ok(part2(input))
+    }
+
+    fn solve(reader: impl BufRead) -> aoc::Result<(Self::Part1, Self::Part2)> {
+        solve(reader)
+    }
+}
+
+impl aoc::Generate for Day01 {
+    fn generate(rng: &mut StdRng, scale: usize) -> String {
+        generate(rng, 2000 * scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE_INPUT: &str = include_str!("../examples/example.txt");
+
+    aoc::example_tests!(Day01);
+
+    #[test]
+    fn test_parse() {
+        let output = parse_input(EXAMPLE_INPUT).unwrap();
+        let expected_output: Vec<u32> =
+            vec![199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+        assert_eq!(output, expected_output);
+    }
+
+    #[test]
+    fn test_parse_tolerant() {
+        let input = "199\n\n200\n2x0\n 208 \n-1\n";
+        let parsed = parse_tolerant(input.as_bytes()).unwrap();
+        assert_eq!(parsed.readings, vec![199, 200, 208]);
+        let skipped: Vec<_> =
+            parsed.skipped.iter().map(|line| line.line).collect();
+        assert_eq!(skipped, vec![2, 4, 6]);
+        let invalid: Vec<_> =
+            parsed.invalid().map(ToString::to_string).collect();
+        assert_eq!(
+            invalid,
+            vec![
+                "line 4: \"2x0\": invalid digit found in string",
+                "line 6: \"-1\": invalid digit found in string",
+            ]
+        );
+
+        let parsed = parse_tolerant(EXAMPLE_INPUT.as_bytes()).unwrap();
+        assert_eq!(parsed.readings, parse_input(EXAMPLE_INPUT).unwrap());
+        assert!(parsed.skipped.is_empty());
+    }
+
+    #[test]
+    fn test_window_changes() {
+        let depths = parse_input(EXAMPLE_INPUT).unwrap();
+        let expected = Changes {
+            increases: 7,
+            decreases: 2,
+            plateaus: 0,
+        };
+        assert_eq!(window_changes(&depths, 1), expected);
+        let expected = Changes {
+            increases: 5,
+            decreases: 1,
+            plateaus: 1,
+        };
+        assert_eq!(window_changes(&depths, 3), expected);
+        assert_eq!(window_changes(&depths, 10), Changes::default());
+        assert_eq!(window_changes(&depths, 0), Changes::default());
+
+        // Sums of these would overflow
+        let depths = [u64::MAX, u64::MAX, 1, u64::MAX, 0];
+        let expected = Changes {
+            increases: 0,
+            decreases: 2,
+            plateaus: 1,
+        };
+        assert_eq!(window_changes(depths, 2), expected);
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(solve(EXAMPLE_INPUT.as_bytes()).unwrap(), (7, 5));
+        assert_eq!(solve("1\n\n 2\n3\n4\n".as_bytes()).unwrap(), (3, 1));
+        assert!(solve("".as_bytes()).is_err());
+
+        let err = solve("1\n2\nx\n".as_bytes()).unwrap_err();
+        assert!(err.to_string().contains("line 3"), "{}", err);
+    }
+
+    aoc::generate_tests!(Day01);
+
+    aoc::rejects_input!(Day01, ["", "\n\n", "199\nabc", "-1", "4294967296"]);
+
+    proptest::proptest! {
+        #[test]
+        fn test_window_changes_matches_sums(
+            depths in proptest::collection::vec(
+                proptest::num::u64::ANY,
+                0..30,
+            ),
+            size in 1_usize..6,
+        ) {
+            let sums: Vec<u128> = depths
+                .windows(size)
+                .map(|window| window.iter().map(|&v| u128::from(v)).sum())
+                .collect();
+            let mut expected = Changes::default();
+            for pair in sums.windows(2) {
+                match pair[1].cmp(&pair[0]) {
+                    Ordering::Greater => expected.increases += 1,
+                    Ordering::Less => expected.decreases += 1,
+                    Ordering::Equal => expected.plateaus += 1,
+                }
+            }
+            proptest::prop_assert_eq!(window_changes(depths, size), expected);
+        }
+    }
+
+    aoc::parse_never_panics!(Day01, "\\PC*|([0-9]{1,4}\n{0,2}){0,20}");
+}
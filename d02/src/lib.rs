@@ -0,0 +1,588 @@
+use aoc::{Error, ParseContext};
+use std::{io::BufRead, str::FromStr};
+
+use aoc::generate::{RngExt, StdRng};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Direction {
+    Forward(i32),
+    Up(i32),
+    Down(i32),
+    /// Move backwards without turning around.
+    Back(i32),
+    /// Turn left on the spot by a number of degrees, a multiple of 90.
+    Left(i32),
+    /// Turn right on the spot by a number of degrees, a multiple of 90.
+    Right(i32),
+}
+pub struct Directions(Vec<Direction>);
+
+impl FromStr for Directions {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let directions =
+            directions(s.as_bytes()).collect::<aoc::Result<Vec<_>>>()?;
+        if directions.is_empty() {
+            return Err(Error::invalid_input("no directions"));
+        }
+        Ok(Directions(directions))
+    }
+}
+
+/// Versions of the command language. Each version only accepts the commands
+/// it knows about, so a log is read the same way no matter what gets added
+/// later.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Grammar {
+    /// `forward`, `up` and `down`, as in the puzzle.
+    #[default]
+    V1,
+    /// Adds `back`, and `left` and `right` turns in degrees.
+    V2,
+}
+
+impl Grammar {
+    /// Parse a single command.
+    pub fn parse(self, s: &str) -> aoc::Result<Direction> {
+        use Direction::*;
+        let mut words = s.split_whitespace();
+        let (command, amount) =
+            match (words.next(), words.next(), words.next()) {
+                (Some(command), Some(amount), None) => (command, amount),
+                _ => {
+                    return Err(Error::parse(
+                        "direction",
+                        format!("Couldn't parse line {}", s),
+                    ))
+                }
+            };
+        let amount = amount.parse()?;
+        Ok(match (self, command) {
+            (_, "forward") => Forward(amount),
+            (_, "up") => Up(amount),
+            (_, "down") => Down(amount),
+            (Grammar::V2, "back") => Back(amount),
+            (Grammar::V2, "left" | "right") if amount % 90 != 0 => {
+                return Err(Error::parse(
+                    "direction",
+                    format!("can only turn by multiples of 90, not {}", s),
+                ))
+            }
+            (Grammar::V2, "left") => Left(amount),
+            (Grammar::V2, "right") => Right(amount),
+            _ => {
+                return Err(Error::parse(
+                    "direction",
+                    format!("{:?} has no command {:?}", self, command),
+                ))
+            }
+        })
+    }
+
+    /// Lazily parse one [Direction] per line of `reader`.
+    pub fn directions(
+        self,
+        reader: impl BufRead,
+    ) -> impl Iterator<Item = aoc::Result<Direction>> {
+        aoc::files::parse_lines::<String>(reader).enumerate().map(
+            move |(idx, line)| {
+                let line = line?;
+                self.parse(&line).parse_context(|| {
+                    format!(
+                        "line {} as {:?} direction: {:?}",
+                        idx + 1,
+                        self,
+                        line
+                    )
+                })
+            },
+        )
+    }
+}
+
+impl FromStr for Direction {
+    type Err = Error;
+
+    /// Parse a command from the puzzle, in [Grammar::V1].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grammar::V1.parse(s)
+    }
+}
+
+impl Directions {
+    fn iter(&self) -> impl Iterator<Item = &Direction> {
+        self.0.iter()
+    }
+}
+
+/// Lazily parse one [Direction] per line of `reader`, in [Grammar::V1].
+pub fn directions(
+    reader: impl BufRead,
+) -> impl Iterator<Item = aoc::Result<Direction>> {
+    Grammar::V1.directions(reader)
+}
+
+fn overflow() -> Error {
+    Error::invalid_input("position overflows an i64")
+}
+
+/// Which way the submarine faces, looking down from the surface. It starts
+/// out facing east, along the horizontal axis.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Heading {
+    #[default]
+    East,
+    North,
+    West,
+    South,
+}
+
+impl Heading {
+    /// Turn anticlockwise by `degrees`, rounded towards zero to a multiple
+    /// of 90.
+    pub fn turned(self, degrees: i32) -> Self {
+        const HEADINGS: [Heading; 4] =
+            [Heading::East, Heading::North, Heading::West, Heading::South];
+        let quarters = (self as i32 + degrees / 90).rem_euclid(4);
+        HEADINGS[quarters as usize]
+    }
+
+    /// How far one unit forward moves along the horizontal and lateral axes.
+    fn unit(self) -> (i64, i64) {
+        match self {
+            Heading::East => (1, 0),
+            Heading::North => (0, 1),
+            Heading::West => (-1, 0),
+            Heading::South => (0, -1),
+        }
+    }
+}
+
+/// Where the submarine is and which way it faces. Only [Grammar::V2] can
+/// turn it away from the horizontal axis.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Position {
+    pub horizontal: i64,
+    pub lateral: i64,
+    pub depth: i64,
+    pub heading: Heading,
+}
+
+impl Position {
+    /// Horizontal position multiplied by depth, the answer to either part.
+    /// This is wide enough that it can't overflow.
+    pub fn product(&self) -> i128 {
+        i128::from(self.horizontal) * i128::from(self.depth)
+    }
+
+    /// Move `amount` along the heading, or backwards if it's negative.
+    pub fn moved(self, amount: i64) -> Option<Self> {
+        let (dx, dy) = self.heading.unit();
+        Some(Self {
+            horizontal: self
+                .horizontal
+                .checked_add(dx.checked_mul(amount)?)?,
+            lateral: self.lateral.checked_add(dy.checked_mul(amount)?)?,
+            ..self
+        })
+    }
+
+    /// Turn anticlockwise by `degrees` without moving.
+    pub fn turned(self, degrees: i32) -> Self {
+        Self {
+            heading: self.heading.turned(degrees),
+            ..self
+        }
+    }
+
+    /// Move `amount` deeper, or shallower if it's negative.
+    pub fn dived(self, amount: i64) -> Option<Self> {
+        Some(Self {
+            depth: self.depth.checked_add(amount)?,
+            ..self
+        })
+    }
+}
+
+/// How the submarine moves for each [Direction]. A model can keep state of
+/// its own between steps, like [Aimed] does.
+pub trait MovementModel {
+    /// Where the submarine ends up after following `direction` from
+    /// `position`, or `None` if that overflows, in which case the model's
+    /// own state should be left as it was.
+    fn step(
+        &mut self,
+        position: Position,
+        direction: &Direction,
+    ) -> Option<Position>;
+}
+
+/// Part 1: up and down change depth directly.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Simple;
+
+impl MovementModel for Simple {
+    fn step(
+        &mut self,
+        position: Position,
+        direction: &Direction,
+    ) -> Option<Position> {
+        match *direction {
+            Direction::Forward(amount) => position.moved(amount.into()),
+            Direction::Back(amount) => position.moved(-i64::from(amount)),
+            Direction::Up(amount) => position.dived(-i64::from(amount)),
+            Direction::Down(amount) => position.dived(amount.into()),
+            Direction::Left(degrees) => Some(position.turned(degrees)),
+            Direction::Right(degrees) => {
+                Some(position.turned(degrees.checked_neg()?))
+            }
+        }
+    }
+}
+
+/// Part 2: up and down change the aim, and moving dives by the aim for each
+/// unit moved forward, or climbs by it for each unit moved back.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Aimed {
+    pub aim: i64,
+}
+
+impl MovementModel for Aimed {
+    fn step(
+        &mut self,
+        position: Position,
+        direction: &Direction,
+    ) -> Option<Position> {
+        let amount = match *direction {
+            Direction::Forward(amount) => i64::from(amount),
+            Direction::Back(amount) => -i64::from(amount),
+            Direction::Up(amount) => {
+                self.aim = self.aim.checked_sub(amount.into())?;
+                return Some(position);
+            }
+            Direction::Down(amount) => {
+                self.aim = self.aim.checked_add(amount.into())?;
+                return Some(position);
+            }
+            _ => return Simple.step(position, direction),
+        };
+        position.moved(amount)?.dived(self.aim.checked_mul(amount)?)
+    }
+}
+
+/// A submarine following directions one at a time under movement model
+/// `M`, starting at the surface.
+#[derive(Clone, Debug, Default)]
+pub struct Submarine<M> {
+    model: M,
+    position: Position,
+}
+
+impl<M: MovementModel> Submarine<M> {
+    pub fn new(model: M) -> Self {
+        Self {
+            model,
+            position: Position::default(),
+        }
+    }
+
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    pub fn model(&self) -> &M {
+        &self.model
+    }
+
+    /// Follow one direction, returning the new position. On overflow the
+    /// submarine stays where it was.
+    pub fn execute(&mut self, direction: &Direction) -> aoc::Result<Position> {
+        self.position = self
+            .model
+            .step(self.position, direction)
+            .ok_or_else(overflow)?;
+        Ok(self.position)
+    }
+
+    /// Follow every direction, returning the final position.
+    pub fn run<'a>(
+        &mut self,
+        directions: impl IntoIterator<Item = &'a Direction>,
+    ) -> aoc::Result<Position> {
+        for direction in directions {
+            self.execute(direction)?;
+        }
+        Ok(self.position)
+    }
+
+    /// Follow every direction, yielding the position after each one so that
+    /// the course can be plotted. Stops after the first error.
+    pub fn trace<'a>(
+        mut self,
+        directions: impl IntoIterator<Item = &'a Direction> + 'a,
+    ) -> impl Iterator<Item = aoc::Result<Position>> + 'a
+    where
+        M: 'a,
+    {
+        let mut failed = false;
+        directions.into_iter().map_while(move |direction| {
+            if failed {
+                return None;
+            }
+            let position = self.execute(direction);
+            failed = position.is_err();
+            Some(position)
+        })
+    }
+}
+
+fn part1(directions: &Directions) -> aoc::Result<i128> {
+    Ok(Submarine::new(Simple).run(directions.iter())?.product())
+}
+
+fn part2(directions: &Directions) -> aoc::Result<i128> {
+    Ok(Submarine::new(Aimed::default())
+        .run(directions.iter())?
+        .product())
+}
+
+/// Solve both parts in a single pass over `reader`, in constant memory.
+pub fn solve(reader: impl BufRead) -> aoc::Result<(i128, i128)> {
+    let mut first = Submarine::new(Simple);
+    let mut second = Submarine::new(Aimed::default());
+    let mut count = 0_usize;
+    for direction in directions(reader) {
+        let direction = direction?;
+        first.execute(&direction)?;
+        second.execute(&direction)?;
+        count += 1;
+    }
+    if count == 0 {
+        return Err(Error::invalid_input("no directions"));
+    }
+    Ok((first.position().product(), second.position().product()))
+}
+
+/// Random commands, going down a little more often than up until the aim
+/// reaches 1000, as in the real input. Capping the aim keeps the depth
+/// growing linearly, so that any number of commands fits in an `i64`.
+pub fn generate(rng: &mut StdRng, commands: usize) -> String {
+    let mut aim = 0;
+    (0..commands)
+        .map(|_| {
+            let amount = rng.random_range(1..10);
+            let direction = match rng.random_range(0..100) {
+                0..=39 => "forward",
+                40..=72 if aim < 1000 => {
+                    aim += amount;
+                    "down"
+                }
+                _ => {
+                    aim -= amount;
+                    "up"
+                }
+            };
+            format!("{} {}\n", direction, amount)
+        })
+        .collect()
+}
+
+pub struct Day02;
+
+impl aoc::Solution for Day02 {
+    const DAY: u8 = 2;
+    const STREAMING: bool = true;
+    type Input = Directions;
+    type Part1 = i128;
+    type Part2 = i128;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Input) -> aoc::Result<Self::Part1> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> aoc::Result<Self::Part2> {
+        part2(input)
+    }
+
+    fn solve(reader: impl BufRead) -> aoc::Result<(Self::Part1, Self::Part2)> {
+        solve(reader)
+    }
+}
+
+impl aoc::Generate for Day02 {
+    fn generate(rng: &mut StdRng, scale: usize) -> String {
+        generate(rng, 1000 * scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    static EXAMPLE_INPUT: &str = include_str!("../examples/example.txt");
+
+    aoc::example_tests!(Day02);
+
+    #[test]
+    fn test_parse() {
+        let directions: Directions = EXAMPLE_INPUT.parse().unwrap();
+        let first = Direction::Forward(5);
+        let last = Direction::Forward(2);
+        assert_eq!(directions.0.first().unwrap(), &first);
+        assert_eq!(directions.0.last().unwrap(), &last);
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(solve(EXAMPLE_INPUT.as_bytes()).unwrap(), (150, 900));
+        assert!(solve("".as_bytes()).is_err());
+
+        let err = solve("up 1\ndown\n".as_bytes()).unwrap_err();
+        assert!(err.to_string().contains("line 2"), "{}", err);
+    }
+
+    #[test]
+    fn test_trace() {
+        let directions: Directions = EXAMPLE_INPUT.parse().unwrap();
+        let depths: Vec<_> = Submarine::new(Aimed::default())
+            .trace(directions.iter())
+            .map(|position| position.unwrap().depth)
+            .collect();
+        assert_eq!(depths, vec![0, 0, 40, 40, 40, 60]);
+
+        let mut submarine = Submarine::new(Simple);
+        let last = submarine.run(directions.iter()).unwrap();
+        assert_eq!(
+            last,
+            Position {
+                horizontal: 15,
+                depth: 10,
+                ..Position::default()
+            }
+        );
+        let traced = Submarine::new(Simple).trace(directions.iter()).last();
+        assert_eq!(traced.unwrap().unwrap(), last);
+
+        // Tracing stops at the first overflow, here the third time the depth
+        // grows by nearly 2^62
+        let directions = [
+            Direction::Down(i32::MAX),
+            Direction::Forward(i32::MAX),
+            Direction::Forward(i32::MAX),
+            Direction::Forward(i32::MAX),
+            Direction::Forward(1),
+        ];
+        let mut trace = Submarine::new(Aimed::default()).trace(&directions);
+        for _ in 0..3 {
+            assert!(trace.next().unwrap().is_ok());
+        }
+        assert!(trace.next().unwrap().is_err());
+        assert!(trace.next().is_none());
+    }
+
+    #[test]
+    fn test_grammar() {
+        assert!(Grammar::V1.parse("back 1").is_err());
+        assert_eq!(Grammar::V2.parse("back 1").unwrap(), Direction::Back(1));
+        assert_eq!(
+            Grammar::V2.parse("left 270").unwrap(),
+            Direction::Left(270)
+        );
+        assert!(Grammar::V2.parse("left 45").is_err());
+        assert!(Grammar::V2.parse("sideways 1").is_err());
+        assert!("right 90".parse::<Direction>().is_err());
+
+        let err = Grammar::V2
+            .directions("back 1\nup\n".as_bytes())
+            .collect::<aoc::Result<Vec<_>>>()
+            .unwrap_err();
+        assert!(err.to_string().contains("line 2"), "{}", err);
+    }
+
+    #[test]
+    fn test_navigation() {
+        let log = "forward 5
+left 90
+forward 3
+back 1
+right 180
+forward 2
+down 4";
+        let directions = Grammar::V2
+            .directions(log.as_bytes())
+            .collect::<aoc::Result<Vec<_>>>()
+            .unwrap();
+        let position = Submarine::new(Simple).run(&directions).unwrap();
+        let expected = Position {
+            horizontal: 5,
+            lateral: 0,
+            depth: 4,
+            heading: Heading::South,
+        };
+        assert_eq!(position, expected);
+
+        let log = "down 2\nforward 3\nback 1\nright 90\nforward 1\n";
+        let directions = Grammar::V2
+            .directions(log.as_bytes())
+            .collect::<aoc::Result<Vec<_>>>()
+            .unwrap();
+        let position =
+            Submarine::new(Aimed::default()).run(&directions).unwrap();
+        let expected = Position {
+            horizontal: 2,
+            lateral: -1,
+            depth: 6,
+            heading: Heading::South,
+        };
+        assert_eq!(position, expected);
+    }
+
+    #[test]
+    fn test_custom_model() {
+        /// Counts moves, ignoring anything but going forward.
+        struct Counting(usize);
+
+        impl MovementModel for Counting {
+            fn step(
+                &mut self,
+                mut position: Position,
+                direction: &Direction,
+            ) -> Option<Position> {
+                self.0 += 1;
+                if let Direction::Forward(amount) = direction {
+                    position.horizontal += i64::from(*amount);
+                }
+                Some(position)
+            }
+        }
+
+        let directions: Directions = EXAMPLE_INPUT.parse().unwrap();
+        let mut submarine = Submarine::new(Counting(0));
+        let position = submarine.run(directions.iter()).unwrap();
+        assert_eq!(position.horizontal, 15);
+        assert_eq!(submarine.model().0, 6);
+    }
+
+    aoc::generate_tests!(Day02, [1, 10, 100]);
+
+    aoc::rejects_input!(
+        Day02,
+        [
+            "",
+            "forward",
+            "forward 5 6",
+            "sideways 5",
+            "up x",
+            "forward 5\n\ndown 2",
+        ]
+    );
+
+    aoc::parse_never_panics!(
+        Day02,
+        "\\PC*|((forward|up|down|back)( [0-9]{1,3}){0,2}\n){0,10}"
+    );
+}
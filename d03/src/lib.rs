@@ -0,0 +1,544 @@
+use aoc::{
+    generate::{rand::seq::index, StdRng},
+    Error,
+};
+use std::{fmt, str::FromStr};
+
+/// Widest reading that fits in a [ReportNumber].
+pub const MAX_WIDTH: usize = u64::BITS as usize;
+
+/// A single reading, packed into a `u64` with its first bit as the most
+/// significant.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ReportNumber {
+    bits: u64,
+    width: usize,
+}
+
+impl FromStr for ReportNumber {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() > MAX_WIDTH {
+            return Err(Error::parse(
+                "report number",
+                format!("more than {} bits", MAX_WIDTH),
+            ));
+        }
+        let bits = s.bytes().try_fold(0, |bits, b| {
+            Ok(bits << 1
+                | match b {
+                    b'0' => 0,
+                    b'1' => 1,
+                    _ => {
+                        return Err(Error::parse(
+                            "report number",
+                            "should only be 1s and 0s in input",
+                        ))
+                    }
+                })
+        })?;
+        Ok(ReportNumber {
+            bits,
+            width: s.len(),
+        })
+    }
+}
+
+/// Readings that are all the same width, which is taken from the first.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Report {
+    width: usize,
+    readings: Vec<u64>,
+}
+impl FromStr for Report {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut numbers =
+            aoc::files::parse_lines::<ReportNumber>(s.as_bytes());
+        let first = numbers
+            .next()
+            .ok_or_else(|| Error::invalid_input("empty report"))??;
+        if first.width == 0 {
+            return Err(Error::invalid_input("report has no bits"));
+        }
+        let mut readings = vec![first.bits];
+        for (idx, number) in numbers.enumerate() {
+            let number = number?;
+            if number.width != first.width {
+                return Err(Error::parse(
+                    format!("line {}", idx + 2),
+                    format!(
+                        "{} bits wide, but the report is {} bits wide",
+                        number.width, first.width
+                    ),
+                ));
+            }
+            readings.push(number.bits);
+        }
+        Ok(Report {
+            width: first.width,
+            readings,
+        })
+    }
+}
+
+impl Report {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn readings(&self) -> &[u64] {
+        &self.readings
+    }
+
+    /// Number of readings with a 1 in each column, indexed by bit from the
+    /// least significant.
+    pub fn column_counts(&self) -> Vec<usize> {
+        let mut counts = vec![0; self.width];
+        for &reading in &self.readings {
+            let mut bits = reading;
+            while bits != 0 {
+                counts[bits.trailing_zeros() as usize] += 1;
+                bits &= bits - 1;
+            }
+        }
+        counts
+    }
+
+    /// All `width` bits set.
+    fn mask(&self) -> u64 {
+        u64::MAX >> (MAX_WIDTH - self.width)
+    }
+
+    /// The most common bit in each column, counting ties as a 1.
+    pub fn gamma(&self) -> u64 {
+        let count = self.readings.len();
+        self.column_counts()
+            .iter()
+            .enumerate()
+            .filter(|&(_, &ones)| ones * 2 >= count)
+            .fold(0, |gamma, (col, _)| gamma | 1 << col)
+    }
+
+    /// The least common bit in each column, counting ties as a 0.
+    pub fn epsilon(&self) -> u64 {
+        !self.gamma() & self.mask()
+    }
+
+    /// The reading left after repeatedly keeping the most common bit, or 1
+    /// on a tie.
+    pub fn o2_rating(&self) -> aoc::Result<u64> {
+        filter_rows(&self.sorted(), self.width, true, |_, _, _| ())
+    }
+
+    /// The reading left after repeatedly keeping the least common bit, or 0
+    /// on a tie.
+    pub fn co2_rating(&self) -> aoc::Result<u64> {
+        filter_rows(&self.sorted(), self.width, false, |_, _, _| ())
+    }
+
+    fn sorted(&self) -> Vec<u64> {
+        let mut sorted = self.readings.clone();
+        sorted.sort_unstable();
+        sorted
+    }
+
+    /// Every value worked out from the report along the way to the answers.
+    pub fn diagnostics(&self) -> aoc::Result<Diagnostics> {
+        if self.readings.is_empty() {
+            return Err(Error::invalid_input("empty report"));
+        }
+        let columns = self
+            .column_counts()
+            .into_iter()
+            .rev()
+            .map(|ones| ColumnCount {
+                ones,
+                zeros: self.readings.len() - ones,
+            })
+            .collect();
+        let sorted = self.sorted();
+        let trace = |most_common| -> aoc::Result<_> {
+            let mut steps = Vec::new();
+            let rating = filter_rows(
+                &sorted,
+                self.width,
+                most_common,
+                |col, kept, eliminated| {
+                    steps.push(FilterStep {
+                        column: self.width - 1 - col,
+                        kept,
+                        eliminated: eliminated.to_vec(),
+                    })
+                },
+            )?;
+            Ok((rating, steps))
+        };
+        let (o2, o2_trace) = trace(true)?;
+        let (co2, co2_trace) = trace(false)?;
+        Ok(Diagnostics {
+            width: self.width,
+            readings: self.readings.len(),
+            columns,
+            gamma: self.gamma(),
+            epsilon: self.epsilon(),
+            o2,
+            co2,
+            o2_trace,
+            co2_trace,
+        })
+    }
+}
+
+/// How many readings have each bit in one column.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ColumnCount {
+    pub ones: usize,
+    pub zeros: usize,
+}
+
+/// Readings eliminated by one column while finding a life support rating.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FilterStep {
+    /// Counting from 0 at the first, most significant, column.
+    pub column: usize,
+    /// The bit that readings which were kept have in this column.
+    pub kept: bool,
+    /// In ascending order.
+    pub eliminated: Vec<u64>,
+}
+
+/// Everything worked out from a [Report], to explain the answers. Displays
+/// as a table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostics {
+    pub width: usize,
+    pub readings: usize,
+    /// Counts for each column, starting from the first.
+    pub columns: Vec<ColumnCount>,
+    pub gamma: u64,
+    pub epsilon: u64,
+    pub o2: u64,
+    pub co2: u64,
+    /// Columns that narrowed down the readings for the oxygen generator
+    /// rating, in the order they were used.
+    pub o2_trace: Vec<FilterStep>,
+    /// Likewise for the CO2 scrubber rating.
+    pub co2_trace: Vec<FilterStep>,
+}
+
+impl Diagnostics {
+    /// The answer to part 1.
+    pub fn power_consumption(&self) -> u128 {
+        u128::from(self.gamma) * u128::from(self.epsilon)
+    }
+
+    /// The answer to part 2.
+    pub fn life_support_rating(&self) -> u128 {
+        u128::from(self.o2) * u128::from(self.co2)
+    }
+
+    fn binary(&self, value: u64) -> String {
+        format!("{:0width$b} ({})", value, value, width = self.width)
+    }
+}
+
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} bit report, {} readings", self.width, self.readings)?;
+        writeln!(f, "{:<8}{:>8}{:>8}", "column", "ones", "zeros")?;
+        for (column, count) in self.columns.iter().enumerate() {
+            writeln!(f, "{:<8}{:>8}{:>8}", column, count.ones, count.zeros)?;
+        }
+        writeln!(f, "{:<8}{}", "gamma", self.binary(self.gamma))?;
+        writeln!(f, "{:<8}{}", "epsilon", self.binary(self.epsilon))?;
+        writeln!(f, "{:<8}{}", "power", self.power_consumption())?;
+        for (name, rating, trace) in [
+            ("o2", self.o2, &self.o2_trace),
+            ("co2", self.co2, &self.co2_trace),
+        ] {
+            writeln!(f, "{:<8}{}", name, self.binary(rating))?;
+            for step in trace {
+                let eliminated: Vec<_> = step
+                    .eliminated
+                    .iter()
+                    .map(|reading| {
+                        format!("{:0width$b}", reading, width = self.width)
+                    })
+                    .collect();
+                writeln!(
+                    f,
+                    "  column {} kept {}, eliminated {}",
+                    step.column,
+                    u8::from(step.kept),
+                    eliminated.join(" ")
+                )?;
+            }
+        }
+        writeln!(f, "{:<8}{}", "life", self.life_support_rating())
+    }
+}
+
+fn part1(report: &Report) -> aoc::Result<u128> {
+    if report.readings.is_empty() {
+        return Err(Error::invalid_input("empty report"));
+    }
+    Ok(u128::from(report.gamma()) * u128::from(report.epsilon()))
+}
+
+/// Narrow `sorted` readings that are `width` bits wide down one bit at a
+/// time, keeping readings with the most common bit (1 on a tie) if
+/// `most_common`, or else the least common (0 on a tie), until only one is
+/// left.
+///
+/// Readings left after each bit share all the bits before it, so being
+/// sorted they stay a contiguous range, with those that have a 0 at the next
+/// bit before those with a 1. Each bit is then a binary search rather than a
+/// pass over every reading.
+///
+/// `eliminated` is called with the bit (from the least significant), the
+/// value of that bit in readings that were kept, and the readings that
+/// weren't, for each bit that narrowed them down.
+fn filter_rows(
+    sorted: &[u64],
+    width: usize,
+    most_common: bool,
+    mut eliminated: impl FnMut(usize, bool, &[u64]),
+) -> aoc::Result<u64> {
+    let mut rows = sorted;
+    for col in (0..width).rev() {
+        if rows.len() <= 1 {
+            break;
+        }
+        let split = rows.partition_point(|&row| row >> col & 1 == 0);
+        let (zeros, ones) = rows.split_at(split);
+        // A column every reading agrees on can't narrow them down, and would
+        // otherwise filter out everything when looking for the least common
+        if ones.is_empty() || zeros.is_empty() {
+            continue;
+        }
+        let keep_ones = (ones.len() >= zeros.len()) == most_common;
+        let (kept, dropped) = if keep_ones {
+            (ones, zeros)
+        } else {
+            (zeros, ones)
+        };
+        eliminated(col, keep_ones, dropped);
+        rows = kept;
+    }
+    match rows {
+        [] => Err(Error::invalid_input("empty report")),
+        [row] => Ok(*row),
+        _ => Err(Error::no_solution(format!(
+            "{} identical readings left after filtering",
+            rows.len()
+        ))),
+    }
+}
+
+fn part2(report: &Report) -> aoc::Result<u128> {
+    let sorted = report.sorted();
+    let o2 = filter_rows(&sorted, report.width, true, |_, _, _| ())?;
+    let co2 = filter_rows(&sorted, report.width, false, |_, _, _| ())?;
+    Ok(u128::from(o2) * u128::from(co2))
+}
+
+/// Random, distinct readings `width` bits wide; repeated readings could
+/// leave nothing to tell them apart in part 2. At most `2^width` readings
+/// are generated, and only the low bits of very wide readings are random.
+pub fn generate(rng: &mut StdRng, width: usize, readings: usize) -> String {
+    let values = 1_usize.checked_shl(width as u32).unwrap_or(usize::MAX);
+    index::sample(rng, values, readings.min(values))
+        .into_iter()
+        .map(|value| format!("{:0width$b}\n", value, width = width))
+        .collect()
+}
+
+pub struct Day03;
+
+impl aoc::Solution for Day03 {
+    const DAY: u8 = 3;
+    type Input = Report;
+    type Part1 = u128;
+    type Part2 = u128;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Input) -> aoc::Result<Self::Part1> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> aoc::Result<Self::Part2> {
+        part2(input)
+    }
+}
+
+/// Readings are 12 bits wide like the real input, plus a bit each time
+/// `scale` doubles, so that at most a quarter of the possible readings are
+/// ever drawn and both parts still have interesting answers.
+impl aoc::Generate for Day03 {
+    fn generate(rng: &mut StdRng, scale: usize) -> String {
+        let width = 12 + scale.next_power_of_two().ilog2() as usize;
+        generate(rng, width.min(MAX_WIDTH), 1000 * scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::{Generate, Solution};
+
+    static TEST_INPUT: &str = include_str!("../examples/example.txt");
+
+    aoc::example_tests!(Day03);
+
+    #[test]
+    fn test_parse() {
+        let parsed: Report = TEST_INPUT.parse().unwrap();
+        assert_eq!(parsed.width, 5);
+        assert_eq!(parsed.readings[0], 0b00100);
+    }
+
+    #[test]
+    fn test_width() {
+        let err = "101\n010\n11\n".parse::<Report>().unwrap_err();
+        assert!(err.to_string().contains("line 3"), "{}", err);
+
+        let wide = "1".repeat(MAX_WIDTH);
+        let report: Report = format!("{}\n{}", wide, wide).parse().unwrap();
+        assert_eq!(report.readings, vec![u64::MAX; 2]);
+        assert!(format!("{}1", wide).parse::<Report>().is_err());
+    }
+
+    #[test]
+    fn test_column_counts() {
+        let report: Report = TEST_INPUT.parse().unwrap();
+        assert_eq!(report.column_counts(), vec![5, 7, 8, 5, 7]);
+        assert_eq!(report.gamma(), 22);
+        assert_eq!(report.epsilon(), 9);
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let report: Report = TEST_INPUT.parse().unwrap();
+        let diagnostics = report.diagnostics().unwrap();
+        assert_eq!(diagnostics.columns[0], ColumnCount { ones: 7, zeros: 5 });
+        assert_eq!((diagnostics.o2, diagnostics.co2), (23, 10));
+        assert_eq!(diagnostics.power_consumption(), 198);
+        assert_eq!(diagnostics.life_support_rating(), 230);
+
+        let eliminated: Vec<_> = diagnostics
+            .o2_trace
+            .iter()
+            .map(|step| step.eliminated.len())
+            .collect();
+        assert_eq!(eliminated, vec![5, 3, 1, 1, 1]);
+        let first = FilterStep {
+            column: 0,
+            kept: true,
+            eliminated: vec![0b00010, 0b00100, 0b00111, 0b01010, 0b01111],
+        };
+        assert_eq!(diagnostics.o2_trace[0], first);
+
+        let table = diagnostics.to_string();
+        assert!(table.contains("gamma   10110 (22)"), "{}", table);
+        assert!(
+            table.contains("  column 0 kept 1, eliminated 00010 00100"),
+            "{}",
+            table
+        );
+    }
+
+    #[test]
+    fn test_wide_report() {
+        let high = format!("{}{}", "1".repeat(32), "0".repeat(32));
+        let input =
+            format!("{}\n{}\n{}", "1".repeat(64), high, "0".repeat(64));
+        let report: Report = input.parse().unwrap();
+        assert_eq!(report.gamma(), 0xffff_ffff_0000_0000);
+        assert_eq!(report.epsilon(), 0x0000_0000_ffff_ffff);
+        assert_eq!(
+            part1(&report).unwrap(),
+            0xffff_ffff_0000_0000 * 0xffff_ffff_u128
+        );
+    }
+
+    #[test]
+    fn test_o2() {
+        let report: Report = TEST_INPUT.parse().unwrap();
+        let expected = 23;
+        assert_eq!(report.o2_rating().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_co2() {
+        let report: Report = TEST_INPUT.parse().unwrap();
+        let expected = 10;
+        assert_eq!(report.co2_rating().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_shared_bit() {
+        let report: Report = "10\n11".parse().unwrap();
+        assert_eq!(report.o2_rating().unwrap(), 0b11);
+        assert_eq!(report.co2_rating().unwrap(), 0b10);
+    }
+
+    aoc::generate_tests!(Day03, [1, 10, 100]);
+
+    #[test]
+    fn test_generate_width() {
+        let input = Day03::generate(&mut aoc::generate::rng(0), 10);
+        assert_eq!(input.lines().count(), 10_000);
+        let report = Day03::parse(&input).unwrap();
+        assert_eq!(report.width(), 16);
+        assert_ne!(Day03::part1(&report).unwrap(), 0);
+        assert_ne!(Day03::part2(&report).unwrap(), 0);
+    }
+
+    aoc::rejects_input!(
+        Day03,
+        ["", "0101\n010", "\n", "01010101010x", "010101010101\n\n"]
+    );
+
+    /// Filter the readings one bit at a time by scanning all of them, as
+    /// the puzzle describes.
+    fn brute_force(report: &Report, most_common: bool) -> Option<u64> {
+        let mut rows = report.readings.clone();
+        for col in (0..report.width).rev() {
+            let ones = rows.iter().filter(|&row| row >> col & 1 == 1).count();
+            let zeros = rows.len() - ones;
+            if rows.len() > 1 && ones > 0 && zeros > 0 {
+                let keep_ones = (ones >= zeros) == most_common;
+                rows.retain(|&row| (row >> col & 1 == 1) == keep_ones);
+            }
+        }
+        match rows[..] {
+            [row] => Some(row),
+            _ => None,
+        }
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_ratings_match_brute_force(
+            input in "([01]{6}\n){1,40}",
+        ) {
+            let report: Report = input.parse().unwrap();
+            proptest::prop_assert_eq!(
+                report.o2_rating().ok(),
+                brute_force(&report, true)
+            );
+            proptest::prop_assert_eq!(
+                report.co2_rating().ok(),
+                brute_force(&report, false)
+            );
+        }
+    }
+
+    aoc::parse_never_panics!(Day03, "\\PC*|([01]{11,13}\n){0,10}");
+}
@@ -0,0 +1,843 @@
+use aoc::{
+    generate::{
+        rand::seq::{index, SliceRandom},
+        StdRng,
+    },
+    Error, ParseContext,
+};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt,
+    str::FromStr,
+};
+
+/// A board of any size, with its cells stored a row at a time.
+#[derive(PartialEq, Debug, Clone)]
+pub struct BingoBoard {
+    width: usize,
+    cells: Vec<u32>,
+    /// Where each number is on the board, so that marking doesn't have to
+    /// search for it.
+    index: HashMap<u32, Vec<usize>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct BingoGame {
+    numbers: Vec<u32>,
+    boards: Vec<BingoBoard>,
+    /// Groups of cells, any one of which wins once they're all marked.
+    patterns: Vec<Vec<usize>>,
+    /// Indices into `patterns` of those including each cell.
+    cell_patterns: Vec<Vec<usize>>,
+}
+
+/// A board winning: the first time any of its patterns are complete.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Winner {
+    /// Index of the board in the game.
+    pub board: usize,
+    /// Index of the number that won, in the order they're drawn.
+    pub turn: usize,
+    pub number: u32,
+    /// Sum of the unmarked numbers times the winning number, which doesn't
+    /// always fit in a `u32` or even a `u64`.
+    pub score: u128,
+}
+
+/// Marks on one board as a game is played.
+#[derive(Debug)]
+struct Marks {
+    marked: Vec<bool>,
+    /// How many cells of each pattern are marked.
+    counts: Vec<usize>,
+    /// A board would need billions of cells for this to overflow.
+    unmarked_sum: u64,
+}
+
+/// Something wrong with the input for a game, on a line counting from 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Issue {
+    /// A row that isn't as wide as the first row of its board. The whole
+    /// board is skipped.
+    RaggedRow {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A board, starting on `line`, that isn't the same size as the first
+    /// board, as `(width, height)`. It's skipped.
+    MismatchedBoard {
+        line: usize,
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    /// A number that's already on the same board. Both are marked when it's
+    /// drawn.
+    Duplicate { line: usize, number: u32 },
+    /// A number on a board that's never drawn, so the board may never win.
+    /// This can happen in a fair game, so it isn't an error.
+    NeverDrawn { line: usize, number: u32 },
+}
+
+impl Issue {
+    pub fn line(&self) -> usize {
+        match *self {
+            Issue::RaggedRow { line, .. }
+            | Issue::MismatchedBoard { line, .. }
+            | Issue::Duplicate { line, .. }
+            | Issue::NeverDrawn { line, .. } => line,
+        }
+    }
+
+    /// Whether parsing strictly rejects the game.
+    pub fn is_error(&self) -> bool {
+        !matches!(self, Issue::NeverDrawn { .. })
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: ", self.line())?;
+        match self {
+            Issue::RaggedRow {
+                expected, found, ..
+            } => write!(
+                f,
+                "row of {} numbers on a board {} wide",
+                found, expected
+            ),
+            Issue::MismatchedBoard {
+                expected, found, ..
+            } => write!(
+                f,
+                "{} by {} board in a game of {} by {} boards",
+                found.0, found.1, expected.0, expected.1
+            ),
+            Issue::Duplicate { number, .. } => {
+                write!(f, "{} is already on this board", number)
+            }
+            Issue::NeverDrawn { number, .. } => {
+                write!(f, "{} is never drawn", number)
+            }
+        }
+    }
+}
+
+impl BingoBoard {
+    /// Parse a board from consecutive `(line number, line)` pairs, recording
+    /// anything wrong with it in `issues`. A ragged board gives `None`.
+    fn parse_lines(
+        lines: &[(usize, &str)],
+        issues: &mut Vec<Issue>,
+    ) -> aoc::Result<Option<Self>> {
+        let mut width = None;
+        let mut ragged = false;
+        let mut cells = Vec::new();
+        let mut index = HashMap::<_, Vec<_>>::new();
+        for &(line, text) in lines {
+            let row = text
+                .split_whitespace()
+                .map(|word| {
+                    word.parse().parse_context(|| {
+                        format!("line {} bingo number {:?}", line, word)
+                    })
+                })
+                .collect::<aoc::Result<Vec<u32>>>()?;
+            let expected = *width.get_or_insert(row.len());
+            if row.len() != expected {
+                issues.push(Issue::RaggedRow {
+                    line,
+                    expected,
+                    found: row.len(),
+                });
+                ragged = true;
+            }
+            for number in row {
+                let positions = index.entry(number).or_default();
+                if !positions.is_empty() {
+                    issues.push(Issue::Duplicate { line, number });
+                }
+                positions.push(cells.len());
+                cells.push(number);
+            }
+        }
+        Ok(match width {
+            Some(width) if width > 0 && !ragged => Some(Self {
+                width,
+                cells,
+                index,
+            }),
+            _ => None,
+        })
+    }
+}
+
+impl FromStr for BingoBoard {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<_> = s
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| (idx + 1, line))
+            .collect();
+        let mut issues = Vec::new();
+        let board = Self::parse_lines(&lines, &mut issues)?;
+        if let Some(issue) = issues.first() {
+            return Err(Error::invalid_input(issue.to_string()));
+        }
+        board.ok_or_else(|| Error::invalid_input("empty bingo board"))
+    }
+}
+
+impl BingoGame {
+    /// Parse a game, skipping boards that can't be played and returning
+    /// [Issue]s with everything that looks wrong, rather than failing on
+    /// them. Boards are separated by any number of blank lines. Only input
+    /// that isn't made of numbers is an error.
+    ///
+    /// Skipped boards are left out of the game, so boards are numbered
+    /// differently from the input when there are any.
+    pub fn parse_tolerant(s: &str) -> aoc::Result<(Self, Vec<Issue>)> {
+        let mut lines =
+            s.lines().enumerate().map(|(idx, line)| (idx + 1, line));
+        let numbers: Vec<u32> = lines
+            .next()
+            .ok_or_else(|| Error::invalid_input("no first line"))?
+            .1
+            .split(',')
+            .map(|word| {
+                word.trim()
+                    .parse::<u32>()
+                    .parse_context(|| format!("drawn number {:?}", word))
+            })
+            .collect::<aoc::Result<_>>()?;
+        let lines: Vec<_> = lines.collect();
+        let mut issues = Vec::new();
+        let mut boards: Vec<BingoBoard> = Vec::new();
+        let drawn: HashSet<_> = numbers.iter().collect();
+        for chunk in lines
+            .split(|(_, line)| line.trim().is_empty())
+            .filter(|chunk| !chunk.is_empty())
+        {
+            let Some(board) = BingoBoard::parse_lines(chunk, &mut issues)?
+            else {
+                continue;
+            };
+            let first_line = chunk[0].0;
+            let size = (board.width, board.height());
+            if let Some(first) = boards.first() {
+                let expected = (first.width, first.height());
+                if size != expected {
+                    issues.push(Issue::MismatchedBoard {
+                        line: first_line,
+                        expected,
+                        found: size,
+                    });
+                    continue;
+                }
+            }
+            for (idx, number) in board.cells.iter().enumerate() {
+                if !drawn.contains(number) && board.index[number][0] == idx {
+                    issues.push(Issue::NeverDrawn {
+                        line: first_line + idx / board.width,
+                        number: *number,
+                    });
+                }
+            }
+            boards.push(board);
+        }
+        issues.sort_by_key(Issue::line);
+        let game = BingoGame {
+            numbers,
+            boards,
+            patterns: Vec::new(),
+            cell_patterns: Vec::new(),
+        };
+        Ok((game.with_rules(&[&Rows, &Columns]), issues))
+    }
+}
+
+impl FromStr for BingoGame {
+    type Err = Error;
+
+    /// Parse a game, failing on any [Issue] that [Issue::is_error].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (game, issues) = Self::parse_tolerant(s)?;
+        match issues.iter().find(|issue| issue.is_error()) {
+            Some(issue) => Err(Error::invalid_input(issue.to_string())),
+            None => Ok(game),
+        }
+    }
+}
+
+/// A way of winning at bingo: a board wins once every cell in any one of
+/// the rule's patterns is marked. Implement this for other variants.
+pub trait WinRule {
+    /// Groups of cells that win together on a `width` by `height` board,
+    /// each cell given by its index counting a row at a time.
+    fn patterns(&self, width: usize, height: usize) -> Vec<Vec<usize>>;
+}
+
+/// Any complete row, as in the puzzle.
+pub struct Rows;
+
+impl WinRule for Rows {
+    fn patterns(&self, width: usize, height: usize) -> Vec<Vec<usize>> {
+        (0..height)
+            .map(|row| (row * width..(row + 1) * width).collect())
+            .collect()
+    }
+}
+
+/// Any complete column, as in the puzzle.
+pub struct Columns;
+
+impl WinRule for Columns {
+    fn patterns(&self, width: usize, height: usize) -> Vec<Vec<usize>> {
+        (0..width)
+            .map(|col| (0..height).map(|row| row * width + col).collect())
+            .collect()
+    }
+}
+
+/// Either complete diagonal. Only square boards have diagonals.
+pub struct Diagonals;
+
+impl WinRule for Diagonals {
+    fn patterns(&self, width: usize, height: usize) -> Vec<Vec<usize>> {
+        if width != height {
+            return Vec::new();
+        }
+        vec![
+            (0..width).map(|i| i * width + i).collect(),
+            (0..width).map(|i| i * width + width - 1 - i).collect(),
+        ]
+    }
+}
+
+/// All four corners.
+pub struct FourCorners;
+
+impl WinRule for FourCorners {
+    fn patterns(&self, width: usize, height: usize) -> Vec<Vec<usize>> {
+        let last_row = (height - 1) * width;
+        let mut corners = vec![0, width - 1, last_row, last_row + width - 1];
+        corners.sort_unstable();
+        corners.dedup();
+        vec![corners]
+    }
+}
+
+/// Every cell on the board.
+pub struct Blackout;
+
+impl WinRule for Blackout {
+    fn patterns(&self, width: usize, height: usize) -> Vec<Vec<usize>> {
+        vec![(0..width * height).collect()]
+    }
+}
+
+impl BingoBoard {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.len() / self.width
+    }
+}
+
+impl Marks {
+    fn new(board: &BingoBoard, patterns: usize) -> Self {
+        Self {
+            marked: vec![false; board.cells.len()],
+            counts: vec![0; patterns],
+            unmarked_sum: board.cells.iter().copied().map(u64::from).sum(),
+        }
+    }
+
+    /// Mark `number` wherever it is on `board`, returning whether that
+    /// completes any of the game's patterns, or `None` if there was nothing
+    /// left to mark.
+    fn mark(
+        &mut self,
+        game: &BingoGame,
+        board: &BingoBoard,
+        number: u32,
+    ) -> Option<bool> {
+        let mut result = None;
+        for &cell in board.index.get(&number).into_iter().flatten() {
+            if std::mem::replace(&mut self.marked[cell], true) {
+                continue;
+            }
+            self.unmarked_sum -= u64::from(number);
+            let won = result.get_or_insert(false);
+            for &pattern in &game.cell_patterns[cell] {
+                self.counts[pattern] += 1;
+                *won |= self.counts[pattern] == game.patterns[pattern].len();
+            }
+        }
+        result
+    }
+}
+
+impl BingoGame {
+    /// Play by `rules` instead, where meeting any one of them wins. The
+    /// puzzle plays by [Rows] and [Columns].
+    pub fn with_rules(mut self, rules: &[&dyn WinRule]) -> Self {
+        let (width, height) = self
+            .boards
+            .first()
+            .map_or((0, 0), |board| (board.width, board.height()));
+        self.patterns = match width * height {
+            0 => Vec::new(),
+            _ => rules
+                .iter()
+                .flat_map(|rule| rule.patterns(width, height))
+                .collect(),
+        };
+        self.cell_patterns = vec![Vec::new(); width * height];
+        for (idx, pattern) in self.patterns.iter().enumerate() {
+            for &cell in pattern {
+                self.cell_patterns[cell].push(idx);
+            }
+        }
+        self
+    }
+
+    /// Play through the numbers once, lazily describing what happens. Boards
+    /// stop being played once they win, and the game ends once every board
+    /// has.
+    pub fn events(&self) -> Events<'_> {
+        Events {
+            game: self,
+            marks: self
+                .boards
+                .iter()
+                .map(|board| Some(Marks::new(board, self.patterns.len())))
+                .collect(),
+            turn: 0,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Play through the whole game, keeping track of who wins.
+    pub fn replay(&self) -> Replay {
+        Replay {
+            winners: self
+                .events()
+                .filter_map(|event| match event {
+                    Event::Won(winner) => Some(winner),
+                    _ => None,
+                })
+                .collect(),
+            boards: self.boards.len(),
+        }
+    }
+}
+
+/// Something that happened while playing a game, from [BingoGame::events].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// A number was drawn, on a turn counting from 0.
+    Drawn { turn: usize, number: u32 },
+    /// A board that hasn't won yet had the number just drawn on it.
+    Marked { turn: usize, board: usize },
+    /// A board won with the number just drawn.
+    Won(Winner),
+}
+
+/// Iterator over the [Event]s of a game.
+pub struct Events<'a> {
+    game: &'a BingoGame,
+    /// `None` once a board has won.
+    marks: Vec<Option<Marks>>,
+    turn: usize,
+    pending: VecDeque<Event>,
+}
+
+impl Iterator for Events<'_> {
+    type Item = Event;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending.is_empty() {
+            let number = *self.game.numbers.get(self.turn)?;
+            if self.marks.iter().all(Option::is_none) {
+                return None;
+            }
+            let turn = self.turn;
+            self.turn += 1;
+            self.pending.push_back(Event::Drawn { turn, number });
+            for (idx, board) in self.game.boards.iter().enumerate() {
+                let Some(marks) = &mut self.marks[idx] else {
+                    continue;
+                };
+                let Some(won) = marks.mark(self.game, board, number) else {
+                    continue;
+                };
+                self.pending.push_back(Event::Marked { turn, board: idx });
+                if won {
+                    self.pending.push_back(Event::Won(Winner {
+                        board: idx,
+                        turn,
+                        number,
+                        score: u128::from(marks.unmarked_sum)
+                            * u128::from(number),
+                    }));
+                    self.marks[idx] = None;
+                }
+            }
+        }
+        self.pending.pop_front()
+    }
+}
+
+/// Who won a game, and when. Boards winning on the same turn are in the
+/// order they're given.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Replay {
+    winners: Vec<Winner>,
+    boards: usize,
+}
+
+impl Replay {
+    /// Every board that won, in the order they won.
+    pub fn winners(&self) -> &[Winner] {
+        &self.winners
+    }
+
+    /// The `n`th board to win, counting from 0.
+    pub fn nth_winner(&self, n: usize) -> Option<&Winner> {
+        self.winners.get(n)
+    }
+
+    /// The first board to win, as in part 1.
+    pub fn first_winner(&self) -> Option<&Winner> {
+        self.winners.first()
+    }
+
+    /// The last board to win, as in part 2, as long as every board does and
+    /// no other board wins at the same time.
+    pub fn last_winner(&self) -> Option<&Winner> {
+        match &self.winners[..] {
+            [.., before, last] if before.turn == last.turn => None,
+            [.., last] if self.winners.len() == self.boards => Some(last),
+            _ => None,
+        }
+    }
+
+    /// Boards that never win, in order.
+    pub fn never_win(&self) -> Vec<usize> {
+        let mut won = vec![false; self.boards];
+        for winner in &self.winners {
+            won[winner.board] = true;
+        }
+        (0..self.boards).filter(|&idx| !won[idx]).collect()
+    }
+}
+
+fn part1(game: &BingoGame) -> Option<u128> {
+    game.replay().first_winner().map(|winner| winner.score)
+}
+
+fn part2(game: &BingoGame) -> Option<u128> {
+    game.replay().last_winner().map(|winner| winner.score)
+}
+
+/// Random boards of distinct numbers below `numbers`, all of which are
+/// drawn in a random order so that every board eventually wins. Boards tied
+/// for last to win are redrawn, as part 2 has no answer otherwise.
+pub fn generate(rng: &mut StdRng, boards: usize, numbers: usize) -> String {
+    let numbers = numbers.max(25);
+    let mut draws: Vec<usize> = (0..numbers).collect();
+    draws.shuffle(rng);
+    let mut drawn_at = vec![0; numbers];
+    for (turn, &number) in draws.iter().enumerate() {
+        drawn_at[number] = turn;
+    }
+    // The turn a board wins on is when its first row or column completes
+    let wins_at = |board: &[usize]| {
+        (0..5)
+            .flat_map(|i| {
+                let row = (0..5).map(move |j| board[i * 5 + j]);
+                let col = (0..5).map(move |j| board[j * 5 + i]);
+                [
+                    row.map(|n| drawn_at[n]).max(),
+                    col.map(|n| drawn_at[n]).max(),
+                ]
+            })
+            .flatten()
+            .min()
+            .unwrap_or_default()
+    };
+
+    let mut boards: Vec<Vec<usize>> = (0..boards)
+        .map(|_| index::sample(rng, numbers, 25).into_vec())
+        .collect();
+    let mut turns: Vec<_> =
+        boards.iter().map(|board| wins_at(board)).collect();
+    while let Some(&last) = turns.iter().max() {
+        let mut tied = (0..turns.len()).filter(|&idx| turns[idx] == last);
+        let Some(idx) = tied.nth(1) else { break };
+        boards[idx] = index::sample(rng, numbers, 25).into_vec();
+        turns[idx] = wins_at(&boards[idx]);
+    }
+
+    let mut output = draws
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(",");
+    output.push('\n');
+    for board in boards {
+        output.push('\n');
+        for row in board.chunks(5) {
+            let row: Vec<_> =
+                row.iter().map(|n| format!("{:>2}", n)).collect();
+            output.push_str(&row.join(" "));
+            output.push('\n');
+        }
+    }
+    output
+}
+
+pub struct Day04;
+
+impl aoc::Solution for Day04 {
+    const DAY: u8 = 4;
+    type Input = BingoGame;
+    type Part1 = u128;
+    type Part2 = u128;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Input) -> aoc::Result<Self::Part1> {
+        part1(input).ok_or_else(|| Error::no_solution("No winner for part 1"))
+    }
+
+    fn part2(input: &Self::Input) -> aoc::Result<Self::Part2> {
+        part2(input).ok_or_else(|| Error::no_solution("No winner for part 2"))
+    }
+}
+
+impl aoc::Generate for Day04 {
+    fn generate(rng: &mut StdRng, scale: usize) -> String {
+        generate(rng, 100 * scale, 100 * scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::localpath;
+
+    fn parse_game(input: &str) -> aoc::Result<BingoGame> {
+        std::fs::read_to_string(localpath!(input))?.parse()
+    }
+
+    aoc::example_tests!(Day04);
+
+    aoc::generate_tests!(Day04);
+
+    #[test]
+    fn test_parse_board() {
+        let board: BingoBoard = "22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19"
+            .parse()
+            .unwrap();
+        assert_eq!((board.width(), board.height()), (5, 5));
+        assert_eq!(board.cells[0], 22);
+        assert_eq!(board.cells[7], 23);
+        assert_eq!(board.cells[24], 19);
+        assert_eq!(board.index[&23], vec![7]);
+
+        let board: BingoBoard = "1 2 3\n4 5 6".parse().unwrap();
+        assert_eq!((board.width(), board.height()), (3, 2));
+        assert!("1 2 3\n4 5".parse::<BingoBoard>().is_err());
+    }
+
+    #[test]
+    fn test_parse_game() {
+        let game: BingoGame = parse_game("input.txt").unwrap();
+        assert!(game.numbers.first().unwrap() == &99);
+        assert!(game.numbers.last().unwrap() == &39);
+        assert!(game.boards.first().unwrap().cells.first().unwrap() == &57);
+        assert!(game.boards.last().unwrap().cells.last().unwrap() == &47);
+    }
+
+    #[test]
+    fn test_parse_tolerant() {
+        let input = "1,2,3,4, 5
+ \n1 2\n3 4\n\n\n\n5 6\n7\n\n1 2 3\n4 5 6\n\n4 3\n4 6";
+        let (game, issues) = BingoGame::parse_tolerant(input).unwrap();
+        assert_eq!(game.numbers, vec![1, 2, 3, 4, 5]);
+        assert_eq!(game.boards.len(), 2);
+        assert_eq!(
+            issues,
+            vec![
+                Issue::RaggedRow {
+                    line: 9,
+                    expected: 2,
+                    found: 1
+                },
+                Issue::MismatchedBoard {
+                    line: 11,
+                    expected: (2, 2),
+                    found: (3, 2)
+                },
+                Issue::Duplicate {
+                    line: 15,
+                    number: 4
+                },
+                Issue::NeverDrawn {
+                    line: 15,
+                    number: 6
+                },
+            ]
+        );
+        assert_eq!(
+            issues[2].to_string(),
+            "line 15: 4 is already on this board"
+        );
+        assert_eq!(game.replay().winners()[1].score, 6 * 4);
+
+        let err = input.parse::<BingoGame>().unwrap_err();
+        assert!(err.to_string().contains("line 9"), "{}", err);
+        let (_, issues) = BingoGame::parse_tolerant("1,3\n1 2\n3 4").unwrap();
+        assert!(!issues.iter().any(Issue::is_error));
+        assert!("1,3\n1 2\n3 4".parse::<BingoGame>().is_ok());
+
+        let err = BingoGame::parse_tolerant("1\n\n1 x").unwrap_err();
+        assert!(err.to_string().contains("line 3"), "{}", err);
+    }
+
+    #[test]
+    fn test_winner() {
+        let game: BingoGame = "0\n\n22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19"
+            .parse()
+            .unwrap();
+        let board = &game.boards[0];
+        let mut marks = Marks::new(board, game.patterns.len());
+        for number in [22, 2, 23, 4, 24, 9, 10, 12] {
+            assert_eq!(marks.mark(&game, board, number), Some(false));
+        }
+        assert_eq!(marks.mark(&game, board, 99), None);
+        // Marking a number twice doesn't count twice
+        assert_eq!(marks.mark(&game, board, 24), None);
+        assert_eq!(marks.unmarked_sum, 300 - 106);
+
+        assert_eq!(marks.mark(&game, board, 13), Some(true));
+
+        let mut marks = Marks::new(board, game.patterns.len());
+        for number in [22, 2, 23, 4, 24, 9, 10, 12] {
+            marks.mark(&game, board, number);
+        }
+        assert_eq!(marks.mark(&game, board, 8), Some(true));
+
+        let game: BingoGame = "70000\n\n70000 70001".parse().unwrap();
+        assert_eq!(part1(&game), Some(70001 * 70000));
+        let game: BingoGame =
+            "1\n\n1 4000000000\n3000000000 2".parse().unwrap();
+        assert_eq!(part1(&game), None);
+        let game: BingoGame = "1,3000000000\n\n1 4000000000\n3000000000 2"
+            .parse()
+            .unwrap();
+        assert_eq!(part1(&game), Some(4_000_000_002 * 3_000_000_000));
+    }
+
+    #[test]
+    fn test_replay() {
+        let game = parse_game("examples/example.txt").unwrap();
+        let replay = game.replay();
+        let order: Vec<_> = replay
+            .winners()
+            .iter()
+            .map(|winner| (winner.board, winner.number))
+            .collect();
+        assert_eq!(order, vec![(2, 24), (0, 16), (1, 13)]);
+        assert_eq!(replay.first_winner().unwrap().score, 4512);
+        assert_eq!(replay.nth_winner(1).unwrap().turn, 13);
+        assert_eq!(replay.last_winner().unwrap().score, 1924);
+        assert!(replay.nth_winner(3).is_none());
+        assert!(replay.never_win().is_empty());
+
+        // 7 is on every board
+        let events: Vec<_> = game.events().take(5).collect();
+        assert_eq!(
+            events,
+            vec![
+                Event::Drawn { turn: 0, number: 7 },
+                Event::Marked { turn: 0, board: 0 },
+                Event::Marked { turn: 0, board: 1 },
+                Event::Marked { turn: 0, board: 2 },
+                Event::Drawn { turn: 1, number: 4 },
+            ]
+        );
+        // The game ends once the last board wins
+        assert_eq!(
+            game.events().last(),
+            Some(Event::Won(*replay.last_winner().unwrap()))
+        );
+
+        let game: BingoGame =
+            "1,2\n\n1 3\n4 5\n\n2 1\n6 7\n\n8 9\n2 3".parse().unwrap();
+        let replay = game.replay();
+        let winners: Vec<_> =
+            replay.winners().iter().map(|winner| winner.board).collect();
+        assert_eq!(winners, vec![1]);
+        assert_eq!(replay.never_win(), vec![0, 2]);
+        assert!(replay.last_winner().is_none());
+    }
+
+    #[test]
+    fn test_rules() {
+        let game: BingoGame =
+            "1,3,5,7,9\n\n1 2 3\n4 5 6\n7 8 9".parse().unwrap();
+        let play =
+            |rules: &[&dyn WinRule]| part1(&game.clone().with_rules(rules));
+        assert_eq!(play(&[&Rows, &Columns]), None);
+        // 3, 5 and 7 complete a diagonal before 1, 5 and 9
+        assert_eq!(play(&[&Diagonals]), Some(29 * 7));
+        assert_eq!(play(&[&FourCorners]), Some(20 * 9));
+        assert_eq!(play(&[&Blackout]), None);
+        assert_eq!(play(&[&Rows, &Diagonals]), play(&[&Diagonals]));
+        let mut blackout = game.clone().with_rules(&[&Blackout]);
+        blackout.numbers.extend([2, 4, 6, 8]);
+        assert_eq!(part2(&blackout), Some(0));
+
+        let wide: BingoGame = "1\n\n1 2".parse().unwrap();
+        let wide = wide.with_rules(&[&Diagonals, &FourCorners]);
+        assert_eq!(wide.patterns, vec![vec![0, 1]]);
+    }
+
+    aoc::rejects_input!(
+        Day04,
+        [
+            "",
+            "1,2,x",
+            "1,2\n\n1 2 3\n4 5",
+            "1,2\n\n1 2\n3 4\n\n1 2 3\n4 5 6",
+            "1,2\n\n1 1\n2 3",
+            "1,2\n\n3 4 5 6\n",
+        ]
+    );
+
+    aoc::parse_never_panics!(
+        Day04,
+        concat!(
+            "\\PC*|",
+            "[0-9]{1,2}(,([0-9]{1,2}|[1-4][0-9]{9})){0,30}",
+            "(\n\n(([0-9]{1,2}|[1-4][0-9]{9}) ){5}\n){0,3}",
+        )
+    );
+}
@@ -0,0 +1,202 @@
+use std::{collections::HashMap, str::FromStr};
+
+use aoc::{
+    generate::{RngExt, StdRng},
+    Error, ParseContext,
+};
+
+pub mod segments;
+
+/// Coordinates can be anywhere, including negative.
+#[derive(Debug, PartialEq)]
+pub struct Point((i64, i64));
+
+#[derive(Debug, PartialEq)]
+pub struct Line {
+    start: Point,
+    stop: Point,
+}
+
+macro_rules! delta {
+    ($tt:ident, $idx:tt) => {{
+        use ::std::cmp::Ordering::*;
+        match ($tt.stop.0.$idx).cmp(&$tt.start.0.$idx) {
+            Greater => 1,
+            Equal => 0,
+            Less => -1,
+        }
+    }};
+}
+
+impl Line {
+    fn traverse(&self) -> impl Iterator<Item = Point> + '_ {
+        let (x_delta, y_delta) = (delta!(self, 0), delta!(self, 1));
+        let mut pos = self.start.0;
+        std::iter::once(Point(self.start.0)).chain(std::iter::from_fn(
+            move || {
+                if pos == self.stop.0 {
+                    None
+                } else {
+                    pos.0 += x_delta;
+                    pos.1 += y_delta;
+                    Some(Point(pos))
+                }
+            },
+        ))
+    }
+}
+
+impl FromStr for Line {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut splitter = s.split(" -> ");
+        let (start, stop): (Point, Point) =
+            match (splitter.next(), splitter.next(), splitter.next()) {
+                (Some(first), Some(second), None) => {
+                    (first.parse()?, second.parse()?)
+                }
+                _ => {
+                    return Err(Error::parse(
+                        "line",
+                        format!("expected `x1,y1 -> x2,y2`, found {:?}", s),
+                    ))
+                }
+            };
+        // `traverse` can only step horizontally, vertically or at 45 degrees
+        let (dx, dy) = (
+            start.0 .0.abs_diff(stop.0 .0),
+            start.0 .1.abs_diff(stop.0 .1),
+        );
+        if dx != 0 && dy != 0 && dx != dy {
+            return Err(Error::invalid_input(format!(
+                "{:?} is not horizontal, vertical or diagonal",
+                s
+            )));
+        }
+        Ok(Line { start, stop })
+    }
+}
+
+impl FromStr for Point {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut iter = s.split(',').map(str::parse);
+        if let (Some(Ok(x)), Some(Ok(y)), None) =
+            (iter.next(), iter.next(), iter.next())
+        {
+            Ok(Self((x, y)))
+        } else {
+            Err(Error::parse(
+                "point",
+                format!("couldn't parse as Point: {}", s),
+            ))
+        }
+    }
+}
+
+/// How many lines cover each point that any line does. Only covered points
+/// are stored, so the diagram is as big as the lines are long, however far
+/// apart they are.
+struct Diagram(HashMap<(i64, i64), u32>);
+
+impl Diagram {
+    fn new(lines: &[Line], include_diagonals: bool) -> Self {
+        let mut counts = HashMap::new();
+        for line in lines {
+            if !include_diagonals {
+                match (delta!(line, 0), delta!(line, 1)) {
+                    (_, 0) | (0, _) => (),
+                    _ => continue,
+                };
+            }
+            for point in line.traverse() {
+                *counts.entry(point.0).or_insert(0) += 1;
+            }
+        }
+        Self(counts)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &u32> {
+        self.0.values()
+    }
+}
+
+fn parse_lines(input: &str) -> aoc::Result<Vec<Line>> {
+    let lines: Vec<_> = input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| {
+            line.parse::<Line>()
+                .parse_context(|| format!("line {}", idx + 1))
+        })
+        .collect::<aoc::Result<_>>()?;
+    if lines.is_empty() {
+        return Err(Error::invalid_input("no lines of vents"));
+    }
+    Ok(lines)
+}
+
+fn solve(diagram: &Diagram) -> u32 {
+    diagram.iter().filter(|&val| *val >= 2).count() as u32
+}
+
+/// Random horizontal, vertical and diagonal lines of vents, with every
+/// coordinate below `extent`.
+pub fn generate(rng: &mut StdRng, lines: usize, extent: usize) -> String {
+    let extent = extent.max(1);
+    (0..lines)
+        .map(|_| {
+            let (x1, y1) =
+                (rng.random_range(0..extent), rng.random_range(0..extent));
+            let (x2, y2) = match rng.random_range(0..3) {
+                0 => (rng.random_range(0..extent), y1),
+                1 => (x1, rng.random_range(0..extent)),
+                _ => {
+                    // Go as far as a random corner allows
+                    let (right, down) =
+                        (rng.random_bool(0.5), rng.random_bool(0.5));
+                    let room_x = if right { extent - 1 - x1 } else { x1 };
+                    let room_y = if down { extent - 1 - y1 } else { y1 };
+                    let len = rng.random_range(0..=room_x.min(room_y));
+                    (
+                        if right { x1 + len } else { x1 - len },
+                        if down { y1 + len } else { y1 - len },
+                    )
+                }
+            };
+            format!("{},{} -> {},{}\n", x1, y1, x2, y2)
+        })
+        .collect()
+}
+
+pub struct Day05;
+
+impl aoc::Solution for Day05 {
+    const DAY: u8 = 5;
+    type Input = Vec<Line>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        parse_lines(input)
+    }
+
+    fn part1(input: &Self::Input) -> aoc::Result<Self::Part1> {
+        Ok(solve(&Diagram::new(input, false)))
+    }
+
+    fn part2(input: &Self::Input) -> aoc::Result<Self::Part2> {
+        Ok(solve(&Diagram::new(input, true)))
+    }
+}
+
+impl aoc::Generate for Day05 {
+    fn generate(rng: &mut StdRng, scale: usize) -> String {
+        generate(rng, 500 * scale, 1000)
+    }
+}
+
+#[cfg(test)]
+mod tests;
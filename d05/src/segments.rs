@@ -0,0 +1,190 @@
+//! Count overlapping points without visiting every point on every line, by
+//! intersecting the lines with each other instead. This only depends on the
+//! number of lines, not how long they are.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::Line;
+
+/// Which way a line runs. Lines of a single point count as horizontal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Direction {
+    Horizontal,
+    Vertical,
+    /// `x - y` is the same all the way along.
+    Down,
+    /// `x + y` is the same all the way along.
+    Up,
+}
+
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Horizontal,
+    Direction::Vertical,
+    Direction::Down,
+    Direction::Up,
+];
+
+impl Direction {
+    /// What all points on a line running this way through `(x, y)` share,
+    /// and where `(x, y)` is along that line.
+    fn locate(self, (x, y): (i128, i128)) -> (i128, i128) {
+        match self {
+            Direction::Horizontal => (y, x),
+            Direction::Vertical => (x, y),
+            Direction::Down => (x - y, x),
+            Direction::Up => (x + y, x),
+        }
+    }
+}
+
+/// A [Line] as the points from `start` to `end` (inclusive) along the
+/// line of its direction with the given key.
+#[derive(Clone, Copy, Debug)]
+struct Segment {
+    direction: Direction,
+    key: i128,
+    start: i128,
+    end: i128,
+}
+
+impl From<&Line> for Segment {
+    fn from(line: &Line) -> Self {
+        let widen = |(x, y): (i64, i64)| (i128::from(x), i128::from(y));
+        let (start, stop) = (widen(line.start.0), widen(line.stop.0));
+        let direction = if start.1 == stop.1 {
+            Direction::Horizontal
+        } else if start.0 == stop.0 {
+            Direction::Vertical
+        } else if (stop.0 > start.0) == (stop.1 > start.1) {
+            Direction::Down
+        } else {
+            Direction::Up
+        };
+        let (key, a) = direction.locate(start);
+        let (_, b) = direction.locate(stop);
+        Segment {
+            direction,
+            key,
+            start: a.min(b),
+            end: a.max(b),
+        }
+    }
+}
+
+impl Segment {
+    fn contains(&self, point: (i128, i128)) -> bool {
+        let (key, position) = self.direction.locate(point);
+        key == self.key && (self.start..=self.end).contains(&position)
+    }
+
+    /// The point where two lines running different ways cross, if it's on
+    /// both of them and on whole coordinates.
+    fn crossing(&self, other: &Segment) -> Option<(i128, i128)> {
+        use Direction::*;
+        let point = match (self.direction, other.direction) {
+            (a, b) if a == b => return None,
+            (Horizontal, Vertical) => (other.key, self.key),
+            (Horizontal, Down) => (other.key + self.key, self.key),
+            (Horizontal, Up) => (other.key - self.key, self.key),
+            (Vertical, Down) => (self.key, self.key - other.key),
+            (Vertical, Up) => (self.key, other.key - self.key),
+            (Down, Up) => {
+                let sum = self.key + other.key;
+                if sum % 2 != 0 {
+                    return None;
+                }
+                (sum / 2, (other.key - self.key) / 2)
+            }
+            _ => return other.crossing(self),
+        };
+        (self.contains(point) && other.contains(point)).then_some(point)
+    }
+}
+
+/// Stretches covered by at least two of the `segments`, which all run along
+/// the same line, as disjoint and sorted inclusive ranges.
+fn shared_ranges(segments: &mut [Segment]) -> Vec<(i128, i128)> {
+    segments.sort_unstable_by_key(|segment| segment.start);
+    let mut shared: Vec<(i128, i128)> = Vec::new();
+    // Furthest any segment so far reaches
+    let mut reach: Option<i128> = None;
+    for segment in segments.iter() {
+        if let Some(reach) = reach {
+            if segment.start <= reach {
+                let overlap = (segment.start, segment.end.min(reach));
+                match shared.last_mut() {
+                    Some(last) if overlap.0 <= last.1 + 1 => {
+                        last.1 = last.1.max(overlap.1)
+                    }
+                    _ => shared.push(overlap),
+                }
+            }
+        }
+        reach =
+            Some(reach.map_or(segment.end, |reach| reach.max(segment.end)));
+    }
+    shared
+}
+
+/// Number of points where at least two `lines` overlap, only counting
+/// diagonal lines if `include_diagonals`. Agrees with rasterising the lines,
+/// as parts 1 and 2 do, but takes time quadratic in the number of lines
+/// rather than linear in their total length.
+pub fn count_overlaps(lines: &[Line], include_diagonals: bool) -> u128 {
+    let segments: Vec<Segment> = lines
+        .iter()
+        .map(Segment::from)
+        .filter(|segment| {
+            include_diagonals
+                || matches!(
+                    segment.direction,
+                    Direction::Horizontal | Direction::Vertical
+                )
+        })
+        .collect();
+
+    let mut collinear = HashMap::<_, Vec<_>>::new();
+    for segment in &segments {
+        collinear
+            .entry((segment.direction, segment.key))
+            .or_default()
+            .push(*segment);
+    }
+    let shared: HashMap<_, _> = collinear
+        .into_iter()
+        .map(|(line, mut segments)| (line, shared_ranges(&mut segments)))
+        .collect();
+
+    let crossings: HashSet<_> = segments
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, a)| {
+            segments[idx + 1..].iter().filter_map(|b| a.crossing(b))
+        })
+        .collect();
+
+    let mut count: i128 = shared
+        .values()
+        .flatten()
+        .map(|(start, end)| end - start + 1)
+        .sum();
+    // A crossing is counted once by itself, but has already been counted
+    // once for each line it was shared along
+    for &point in &crossings {
+        let counted = DIRECTIONS
+            .iter()
+            .filter(|&&direction| {
+                let (key, position) = direction.locate(point);
+                shared.get(&(direction, key)).is_some_and(|ranges| {
+                    let idx =
+                        ranges.partition_point(|&(_, end)| end < position);
+                    ranges
+                        .get(idx)
+                        .is_some_and(|&(start, _)| start <= position)
+                })
+            })
+            .count();
+        count += 1 - counted as i128;
+    }
+    count as u128
+}
@@ -1,15 +1,13 @@
 use super::*;
+use aoc::Solution;
 
-static EXAMPLE_INPUT: &str = "0,9 -> 5,9
-8,0 -> 0,8
-9,4 -> 3,4
-2,2 -> 2,1
-7,0 -> 7,4
-6,4 -> 2,0
-0,9 -> 2,9
-3,4 -> 1,4
-0,0 -> 8,8
-5,5 -> 8,2";
+fn parse_input(input: &str, include_diagonals: bool) -> aoc::Result<Diagram> {
+    Ok(Diagram::new(&parse_lines(input)?, include_diagonals))
+}
+
+static EXAMPLE_INPUT: &str = include_str!("../examples/example.txt");
+
+aoc::example_tests!(Day05);
 
 #[test]
 fn test_parse_point() {
@@ -29,18 +27,23 @@ fn test_parse_line() {
 }
 
 #[test]
-fn test_part1() {
-    let expected = 5;
+fn test_parse_input() {
     let diagram = parse_input(EXAMPLE_INPUT, false).unwrap();
-    assert_eq!(solve(&diagram), expected);
+    assert_eq!(diagram.0.len(), 21);
+    assert_eq!(diagram.0[&(0, 9)], 2);
+    assert!(!diagram.0.contains_key(&(0, 0)));
 }
 
 #[test]
-fn test_parse_input() {
-    let diagram = parse_input(EXAMPLE_INPUT, false).unwrap();
-    assert_eq!(diagram.0.len(), 10);
-    assert_eq!(diagram.0[0].len(), 10);
-    assert_eq!(diagram.0[9][0], 2);
+fn test_sparse() {
+    let input = "1000000000000,5 -> 1000000000000,7
+999999999999,6 -> 1000000000001,6
+-5,-5 -> -1,-1
+-1,-5 -> -5,-1";
+    let lines = Day05::parse(input).unwrap();
+    assert_eq!(Day05::part1(&lines).unwrap(), 1);
+    assert_eq!(Day05::part2(&lines).unwrap(), 2);
+    assert_eq!("-3,4".parse::<Point>().unwrap(), Point((-3, 4)));
 }
 
 #[test]
@@ -56,8 +59,62 @@ fn test_traverse() {
 }
 
 #[test]
-fn test_part2() {
-    let expected = 12;
-    let diagram = parse_input(EXAMPLE_INPUT, true).unwrap();
-    assert_eq!(solve(&diagram), expected);
+fn test_count_overlaps() {
+    let lines = parse_lines(EXAMPLE_INPUT).unwrap();
+    assert_eq!(segments::count_overlaps(&lines, false), 5);
+    assert_eq!(segments::count_overlaps(&lines, true), 12);
+
+    // Collinear overlaps, with a crossing inside one of them, and diagonals
+    // that cross between whole coordinates
+    let input = "0,0 -> 10,0
+5,0 -> 20,0
+7,0 -> 8,0
+7,-3 -> 7,3
+0,1 -> 1,0
+0,0 -> 1,1
+-1000000000000,5 -> 1000000000000,5
+0,-1000000000000 -> 0,1000000000000";
+    let lines = parse_lines(input).unwrap();
+    assert_eq!(segments::count_overlaps(&lines, false), 6 + 2);
+    assert_eq!(segments::count_overlaps(&lines, true), 6 + 4);
+}
+
+aoc::generate_tests!(Day05);
+
+aoc::rejects_input!(
+    Day05,
+    [
+        "",
+        "0,9",
+        "0,9 -> 5",
+        "0,9 -> 5,9 -> 1,1",
+        "a,b -> c,d",
+        "0,0 -> 1,3",
+    ]
+);
+
+proptest::proptest! {
+    #[test]
+    fn test_count_overlaps_matches_diagram(
+        s in "((-?[0-9]),(-?[0-9]) -> (-?[0-9]),(-?[0-9])\n){1,12}",
+    ) {
+        // Keep only the lines the puzzle allows
+        let lines: Vec<Line> =
+            s.lines().filter_map(|line| line.parse().ok()).collect();
+        for include_diagonals in [false, true] {
+            let diagram = Diagram::new(&lines, include_diagonals);
+            proptest::prop_assert_eq!(
+                segments::count_overlaps(&lines, include_diagonals),
+                u128::from(solve(&diagram))
+            );
+        }
+    }
 }
+
+aoc::parse_never_panics!(
+    Day05,
+    concat!(
+        "\\PC*|",
+        "([0-9]{1,2},[0-9]{1,2}( -> [0-9]{1,2},[0-9]{1,2})?\n){0,10}",
+    )
+);
@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+
+use aoc::generate::{RngExt, StdRng};
+
+fn recurse(
+    days_left: u32,
+    reproduce_in: u8,
+    cache: &mut HashMap<u32, u64>,
+) -> u64 {
+    if reproduce_in == 0 {
+        // Only fish about to reproduce are cached, keyed by the days left
+        if let Some(val) = cache.get(&days_left) {
+            return *val;
+        }
+        let result = recurse(days_left.saturating_sub(7), 0, cache)
+            + recurse(days_left.saturating_sub(9), 0, cache);
+        cache.insert(days_left, result);
+        return result;
+    }
+    recurse(days_left.saturating_sub(reproduce_in.into()), 0, cache)
+}
+
+fn solve(fish: &[u8], days: u32, cache: &mut HashMap<u32, u64>) -> u64 {
+    cache.insert(0, 1);
+    fish.iter().map(|&f| recurse(days, f, cache)).sum()
+}
+
+fn parse_input(input: &str) -> aoc::Result<Vec<u8>> {
+    Ok(input
+        .trim()
+        .split(',')
+        .map(str::parse::<u8>)
+        .collect::<Result<_, _>>()?)
+}
+
+/// Random lanternfish with 1 to 5 days until they reproduce, as in the real
+/// input.
+pub fn generate(rng: &mut StdRng, fish: usize) -> String {
+    let mut output = (0..fish)
+        .map(|_| rng.random_range(1..=5).to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    output.push('\n');
+    output
+}
+
+pub struct Day06;
+
+impl aoc::Solution for Day06 {
+    const DAY: u8 = 6;
+    type Input = Vec<u8>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> aoc::Result<Self::Part1> {
+        Ok(solve(input, 80, &mut HashMap::new()))
+    }
+
+    fn part2(input: &Self::Input) -> aoc::Result<Self::Part2> {
+        Ok(solve(input, 256, &mut HashMap::new()))
+    }
+}
+
+impl aoc::Generate for Day06 {
+    fn generate(rng: &mut StdRng, scale: usize) -> String {
+        generate(rng, 300 * scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    static EXAMPLE_INPUT: &str = include_str!("../examples/example.txt");
+
+    aoc::example_tests!(Day06);
+
+    aoc::generate_tests!(Day06);
+
+    #[test]
+    fn test_parse_input() {
+        let fish = parse_input(EXAMPLE_INPUT).unwrap();
+        assert_eq!(fish.first().unwrap(), &3_u8);
+        assert_eq!(fish.last().unwrap(), &2_u8);
+    }
+
+    #[test]
+    fn test_solve() {
+        let mut cache = HashMap::new();
+        let fish = parse_input(EXAMPLE_INPUT).unwrap();
+        assert_eq!(solve(&fish, 18, &mut cache), 26);
+        assert_eq!(solve(&fish, 80, &mut cache), 5934);
+        assert_eq!(solve(&fish, 256, &mut cache), 26984457539);
+    }
+
+    aoc::rejects_input!(Day06, ["", "3,,4", "3,x", "256"]);
+
+    /// Step every fish through each day, exactly as the puzzle describes.
+    fn simulate(fish: &[u8], days: u32) -> u64 {
+        let mut fish = fish.to_vec();
+        for _ in 0..days {
+            let mut spawned = 0;
+            for timer in fish.iter_mut() {
+                if *timer == 0 {
+                    *timer = 6;
+                    spawned += 1;
+                } else {
+                    *timer -= 1;
+                }
+            }
+            fish.extend(std::iter::repeat_n(8, spawned));
+        }
+        fish.len() as u64
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_solve_matches_simulation(
+            fish in proptest::collection::vec(0_u8..=8, 0..10),
+            days in 0_u32..60,
+            warmup_days in 0_u32..60,
+        ) {
+            let expected = simulate(&fish, days);
+            proptest::prop_assert_eq!(
+                solve(&fish, days, &mut HashMap::new()),
+                expected
+            );
+
+            // The cache only depends on the days left, so can be shared
+            let mut cache = HashMap::new();
+            solve(&fish, warmup_days, &mut cache);
+            proptest::prop_assert_eq!(
+                solve(&fish, days, &mut cache),
+                expected
+            );
+        }
+    }
+
+    aoc::parse_never_panics!(Day06, "\\PC*|[0-9]{1,2}(,[0-9]{1,2}){0,20}");
+}
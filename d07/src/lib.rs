@@ -0,0 +1,185 @@
+use aoc::{
+    generate::{RngExt, StdRng},
+    Error,
+};
+
+fn parse_input(input: &str) -> aoc::Result<Vec<i32>> {
+    Ok(input
+        .trim()
+        .split(',')
+        .map(str::parse)
+        .collect::<Result<_, _>>()?)
+}
+
+/// Fuel for every crab to move to `location`, paying `cost(n)` to move `n`
+/// steps, or `None` if the total overflows a `u64`.
+fn fuel(
+    crabs: &[i32],
+    location: i64,
+    cost: impl Fn(u64) -> u64,
+) -> Option<u64> {
+    crabs.iter().try_fold(0_u64, |total, &crab| {
+        total.checked_add(cost((i64::from(crab) - location).unsigned_abs()))
+    })
+}
+
+/// Moving `n` steps costs `1 + 2 + ... + n`. Crabs are `i32`s, so `n` is at
+/// most `u32::MAX` and `n * (n + 1)` still fits in a `u64`.
+fn triangular(n: u64) -> u64 {
+    n * (n + 1) / 2
+}
+
+fn part2(crabs: &[i32]) -> aoc::Result<u64> {
+    let min = *crabs
+        .iter()
+        .min()
+        .ok_or_else(|| Error::invalid_input("no crabs"))?;
+    let max = *crabs
+        .iter()
+        .max()
+        .ok_or_else(|| Error::invalid_input("no crabs"))?;
+
+    // The cheapest location is within half a step of the mean, so only the
+    // whole locations either side of that need checking
+    let sum: i128 = crabs.iter().copied().map(i128::from).sum();
+    let mean = sum.div_euclid(crabs.len() as i128) as i64;
+    ((mean - 1).max(min.into())..=(mean + 2).min(max.into()))
+        .filter_map(|location| fuel(crabs, location, triangular))
+        .min()
+        .ok_or_else(|| Error::invalid_input("fuel overflows a u64"))
+}
+
+fn part1(crabs: &[i32]) -> aoc::Result<u64> {
+    // Any median is a cheapest location
+    let mut sorted = crabs.to_vec();
+    sorted.sort_unstable();
+    let median = *sorted
+        .get(sorted.len().saturating_sub(1) / 2)
+        .ok_or_else(|| Error::invalid_input("no crabs"))?;
+
+    fuel(crabs, median.into(), |steps| steps)
+        .ok_or_else(|| Error::invalid_input("fuel overflows a u64"))
+}
+
+/// Random crab positions below `extent`, bunched towards the start like the
+/// real input.
+pub fn generate(rng: &mut StdRng, crabs: usize, extent: u32) -> String {
+    let extent = extent.max(1);
+    let mut output = (0..crabs)
+        .map(|_| {
+            let position = u64::from(rng.random_range(0..extent));
+            (position * position / u64::from(extent)).to_string()
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    output.push('\n');
+    output
+}
+
+pub struct Day07;
+
+impl aoc::Solution for Day07 {
+    const DAY: u8 = 7;
+    type Input = Vec<i32>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> aoc::Result<Self::Part1> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> aoc::Result<Self::Part2> {
+        part2(input)
+    }
+}
+
+impl aoc::Generate for Day07 {
+    fn generate(rng: &mut StdRng, scale: usize) -> String {
+        generate(rng, 1000 * scale, 2000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    static EXAMPLE_INPUT: &str = include_str!("../examples/example.txt");
+
+    aoc::example_tests!(Day07);
+
+    aoc::generate_tests!(Day07, [1, 10, 100]);
+
+    #[test]
+    fn test_parse_input() {
+        let crabs = parse_input(EXAMPLE_INPUT).unwrap();
+        assert_eq!(crabs.first().unwrap(), &16_i32);
+        assert_eq!(crabs.last().unwrap(), &14_i32);
+    }
+
+    #[test]
+    fn test_part1() {
+        let crabs = vec![8, 8, 10];
+        assert_eq!(part1(&crabs).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert_eq!(part2(&[0, 100_000]).unwrap(), 2_500_050_000);
+        let crabs = [-2_000_000_000, 2_000_000_000];
+        assert_eq!(part1(&crabs).unwrap(), 4_000_000_000);
+        assert_eq!(part2(&crabs).unwrap(), 4_000_000_002_000_000_000);
+
+        // Wherever they meet, half of these crabs move about 2^31 steps for
+        // about 2^61 fuel each
+        let crabs = [i32::MIN, i32::MAX].repeat(4);
+        assert_eq!(part1(&crabs).unwrap(), 4 * u64::from(u32::MAX));
+        assert!(part2(&crabs).is_err());
+    }
+
+    aoc::rejects_input!(Day07, ["", "16,,1", "16,x", "2147483648"]);
+
+    /// Walk every crab to every position near the swarm one step at a time,
+    /// paying `cost(n)` for its `n`th step, and keep the cheapest position.
+    fn brute_force(crabs: &[i32], cost: impl Fn(u64) -> u64) -> Option<u64> {
+        let min = *crabs.iter().min()?;
+        let max = *crabs.iter().max()?;
+        ((min - 5)..=(max + 5))
+            .map(|target| {
+                let mut fuel = 0;
+                for &crab in crabs {
+                    let (mut pos, mut steps) = (crab, 0);
+                    while pos != target {
+                        pos += (target - pos).signum();
+                        steps += 1;
+                        fuel += cost(steps);
+                    }
+                }
+                fuel
+            })
+            .min()
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_parts_match_brute_force(
+            crabs in proptest::collection::vec(-50_i32..50, 1..20),
+        ) {
+            proptest::prop_assert_eq!(
+                part1(&crabs).ok(),
+                brute_force(&crabs, |_| 1)
+            );
+            proptest::prop_assert_eq!(
+                part2(&crabs).ok(),
+                brute_force(&crabs, |steps| steps)
+            );
+        }
+    }
+
+    aoc::parse_never_panics!(
+        Day07,
+        "\\PC*|-?[0-9]{1,10}(,-?[0-9]{1,10}){0,20}"
+    );
+}
@@ -0,0 +1,309 @@
+use aoc::{
+    generate::{rand::seq::SliceRandom, RngExt, StdRng},
+    parse_input, Error,
+};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+fn part1(input: &[String]) -> usize {
+    let map = base_known_map();
+    input
+        .iter()
+        .flat_map(|line| {
+            let mut iter =
+                line.split_whitespace().skip_while(|word| word != &"|");
+            let _bar = iter.next();
+            iter
+        })
+        .filter(|word| {
+            let len = word.chars().count();
+            matches!(map.get(&(len as u8)), Some(&Display::Solved(_)))
+        })
+        .count()
+}
+
+#[derive(Debug)]
+struct AnswerKey(HashMap<BTreeSet<char>, Display>);
+
+impl AnswerKey {
+    fn new<'a>(
+        words: impl Iterator<Item = &'a BTreeSet<char>>,
+    ) -> aoc::Result<Self> {
+        let mut hm = HashMap::new();
+
+        let defaults = base_known_map();
+        for word in words {
+            if let Some(val) = defaults.get(&(word.len() as u8)) {
+                hm.insert(word.clone(), val.clone());
+            } else {
+                return Err(Error::invalid_input(format!(
+                    "no default for length {}",
+                    word.len()
+                )));
+            }
+        }
+        Ok(Self(hm))
+    }
+
+    fn get<T>(&self, key: T) -> Option<&Display>
+    where
+        T: Iterator<Item = char>,
+    {
+        let key: BTreeSet<char> = key.collect();
+        self.0.get(&key)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (&BTreeSet<char>, &Display)> {
+        self.0.iter()
+    }
+
+    fn find_word_for(&self, val: u8) -> Option<BTreeSet<char>> {
+        self.iter()
+            .find(|(_, v)| **v == Display::Solved(val))
+            .map(|(k, _)| k.clone())
+    }
+
+    fn solve(&mut self) -> aoc::Result<()> {
+        // Solved by default in `base_known_map`, as long as the input has a
+        // word of the right length
+        let [one, four, eight] = [1, 4, 8].map(|val| {
+            self.find_word_for(val).ok_or_else(|| {
+                Error::invalid_input(format!("no pattern for {}", val))
+            })
+        });
+        let (one, four, eight) = (one?, four?, eight?);
+
+        for (word, disp) in self.0.iter_mut() {
+            match disp {
+                Display::Solved(_) => continue,
+                Display::Unsolved => {
+                    *disp = match word.len() {
+                        // 2, 3, and 5 have 5 segments
+                        5 => {
+                            match (
+                                word.intersection(&one).count(),
+                                word.intersection(&four).count(),
+                            ) {
+                                (2, _) => Display::Solved(3),
+                                (1, 2) => Display::Solved(2),
+                                (1, 3) => Display::Solved(5),
+                                _ => return Err(unknown_pattern(word)),
+                            }
+                        }
+
+                        // 0, 6, and 9 have 6 segments
+                        6 => {
+                            let empty_segment = eight
+                                .difference(word)
+                                .next()
+                                .expect(
+                                "unreachable: already matched on 6 segments",
+                            );
+
+                            match (
+                                word.intersection(&one).count(),
+                                four.contains(empty_segment),
+                            ) {
+                                (1, _) => {
+                                    // 0 and 9 both share 2 segments with 1
+                                    Display::Solved(6)
+                                }
+                                (2, true) => Display::Solved(0),
+                                (2, false) => Display::Solved(9),
+                                _ => return Err(unknown_pattern(word)),
+                            }
+                        }
+                        _ => unreachable!("all digits should be solved"),
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn unknown_pattern(word: &BTreeSet<char>) -> Error {
+    Error::invalid_input(format!(
+        "{} doesn't match any digit",
+        word.iter().collect::<String>()
+    ))
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Display {
+    Unsolved,
+    Solved(u8),
+}
+
+fn decode_numbers(input: &str) -> aoc::Result<AnswerKey> {
+    let mut iter = input.split_whitespace();
+    let first_half: HashSet<BTreeSet<char>> = iter
+        .by_ref()
+        .take_while(|word| word != &"|")
+        .map(|word| BTreeSet::from_iter(word.chars()))
+        .collect();
+    let output_values: HashSet<BTreeSet<char>> =
+        iter.map(|word| BTreeSet::from_iter(word.chars())).collect();
+    let mut ak =
+        AnswerKey::new(first_half.iter().chain(output_values.iter()))?;
+
+    ak.solve()?;
+    Ok(ak)
+}
+fn part2(input: &[String]) -> aoc::Result<u32> {
+    input
+        .iter()
+        .map(|line| {
+            let ak = decode_numbers(line)?;
+            let mut iter = line.split_whitespace().skip_while(|v| v != &"|");
+            let _separator = iter.next();
+            let coded: Vec<_> = iter.collect();
+            coded
+                .iter()
+                .map(|code| {
+                    if let Some(Display::Solved(v)) = ak.get(code.chars()) {
+                        Ok(v.to_string())
+                    } else {
+                        Err(Error::invalid_input(format!(
+                            "unknown code: {}",
+                            code
+                        )))
+                    }
+                })
+                .collect::<aoc::Result<String>>()?
+                .parse::<u32>()
+                .map_err(Into::into)
+        })
+        .try_fold(0_u32, |sum, value: aoc::Result<u32>| {
+            sum.checked_add(value?).ok_or_else(|| {
+                Error::invalid_input("sum of output values overflows a u32")
+            })
+        })
+}
+
+/// Generate defaults based on the number of segments in a "word". Some can be
+/// solved based on this information alone (see `part1`) whereas others can be
+/// narrowed down. The resulting map is {num_segments: possible_numbers}
+fn base_known_map() -> HashMap<u8, Display> {
+    let mut hm = <HashMap<u8, Display>>::new();
+    for (num_segments, val) in [(7, 8), (2, 1), (3, 7), (4, 4)] {
+        hm.insert(num_segments, Display::Solved(val));
+    }
+    for (num_segments, val) in [(6, Display::Unsolved), (5, Display::Unsolved)]
+    {
+        hm.insert(num_segments, val);
+    }
+    hm
+}
+
+/// Segments lit for each digit, before the wires are mixed up.
+const DIGITS: [&str; 10] = [
+    "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf",
+    "abcdefg", "abcdfg",
+];
+
+/// Random displays, each with its own mix up of wires, showing all ten
+/// digits in a random order and then four random digits.
+pub fn generate(rng: &mut StdRng, displays: usize) -> String {
+    let mut output = String::new();
+    for _ in 0..displays {
+        let mut wires: Vec<char> = ('a'..='g').collect();
+        wires.shuffle(rng);
+        let pattern = |digit: usize, rng: &mut StdRng| {
+            let mut segments: Vec<char> = DIGITS[digit]
+                .bytes()
+                .map(|segment| wires[usize::from(segment - b'a')])
+                .collect();
+            segments.shuffle(rng);
+            segments.into_iter().collect::<String>()
+        };
+
+        let mut digits: Vec<usize> = (0..10).collect();
+        digits.shuffle(rng);
+        let patterns: Vec<_> = digits
+            .into_iter()
+            .map(|digit| pattern(digit, rng))
+            .collect();
+        let shown: Vec<_> = (0..4)
+            .map(|_| pattern(rng.random_range(0..10), rng))
+            .collect();
+        output.push_str(&format!(
+            "{} | {}\n",
+            patterns.join(" "),
+            shown.join(" ")
+        ));
+    }
+    output
+}
+
+pub struct Day08;
+
+impl aoc::Solution for Day08 {
+    const DAY: u8 = 8;
+    type Input = Vec<String>;
+    type Part1 = usize;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        let lines = parse_input!(input)?;
+        if lines.is_empty() {
+            return Err(Error::invalid_input("no displays"));
+        }
+        Ok(lines)
+    }
+
+    fn part1(input: &Self::Input) -> aoc::Result<Self::Part1> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> aoc::Result<Self::Part2> {
+        part2(input)
+    }
+}
+
+impl aoc::Generate for Day08 {
+    fn generate(rng: &mut StdRng, scale: usize) -> String {
+        generate(rng, 200 * scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE_INPUT: &str = include_str!("../examples/example.txt");
+
+    aoc::example_tests!(Day08);
+
+    aoc::generate_tests!(Day08);
+
+    #[test]
+    fn test_decode_numbers() {
+        let lines = parse_input!(EXAMPLE_INPUT).unwrap();
+        let ak = decode_numbers(lines.first().unwrap()).unwrap();
+        assert_eq!(ak.get("be".chars()), Some(&Display::Solved(1)));
+        assert_eq!(ak.get("edb".chars()), Some(&Display::Solved(7)));
+    }
+
+    #[test]
+    fn test_part2_overflow() {
+        let line = "ab abc abcd abcdefg | abcdefg abcdefg abcdefg abcdefg \
+                    abcdefg abcdefg abcdefg abcdefg abcdefg";
+        assert_eq!(part2(&[line.to_string()]).unwrap(), 888_888_888);
+        assert!(part2(&vec![line.to_string(); 5]).is_err());
+    }
+
+    aoc::rejects_input!(
+        Day08,
+        [
+            "",
+            "ab | ab",
+            "abcdefgh | ab",
+            "ab abc abcd abcdefg cdefg | cdefg",
+        ]
+    );
+
+    aoc::parse_never_panics!(
+        Day08,
+        "\\PC*|([a-g]{1,7} ){0,10}\\|( [a-g]{1,7}){0,4}"
+    );
+}
@@ -0,0 +1,306 @@
+use aoc::{
+    generate::{RngExt, StdRng},
+    Error,
+};
+use std::{collections::HashSet, str::FromStr};
+
+fn part1(map: &Map) -> u32 {
+    map.low_points().map(|(_, val)| val as u32 + 1).sum()
+}
+
+fn part2(map: &Map) -> aoc::Result<u32> {
+    let mut basins_by_size: Vec<_> = map.basins().map(|b| b.len()).collect();
+    basins_by_size.sort_unstable();
+    basins_by_size.reverse();
+    let largest = &basins_by_size[..basins_by_size.len().min(3)];
+    if largest.is_empty() {
+        return Err(Error::no_solution("Not enough basins found"));
+    }
+    largest
+        .iter()
+        .try_fold(1_u32, |product, &size| {
+            product.checked_mul(size.try_into().ok()?)
+        })
+        .ok_or_else(|| {
+            Error::invalid_input("product of basin sizes overflows a u32")
+        })
+}
+
+/// A height map of any size, with every row as wide as the first.
+pub struct Map {
+    width: usize,
+    /// Heights row by row.
+    cells: Vec<u8>,
+}
+
+impl Map {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.len() / self.width
+    }
+
+    /// get the value at index (x, y)
+    fn get(&self, index: (usize, usize)) -> Option<u8> {
+        if index.0 >= self.width {
+            return None;
+        }
+        let idx = index.1.checked_mul(self.width)?.checked_add(index.0)?;
+        self.cells.get(idx).copied()
+    }
+
+    fn neighbors(
+        &self,
+        index: (usize, usize),
+    ) -> impl Iterator<Item = ((usize, usize), u8)> + '_ {
+        let deltas = [(0, 1), (1, 0)];
+        let ops = [usize::checked_sub, usize::checked_add];
+        ops.into_iter().flat_map(move |op| {
+            deltas
+                .iter()
+                .filter_map(|(x, y)| {
+                    match (op(index.0, *x), op(index.1, *y)) {
+                        (Some(x), Some(y)) => {
+                            self.get((x, y)).map(|v| ((x, y), v))
+                        }
+                        _ => None,
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+    }
+
+    fn items(&self) -> impl Iterator<Item = ((usize, usize), u8)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(idx, val)| ((idx % self.width, idx / self.width), *val))
+    }
+
+    fn low_points(&self) -> impl Iterator<Item = ((usize, usize), u8)> + '_ {
+        self.items().filter_map(|(idx, val)| {
+            // A lone cell has no neighbors, so counts as a low point
+            let min_neighbor = self.neighbors(idx).map(|(_, v)| v).min();
+            if min_neighbor.is_none_or(|min| val < min) {
+                Some((idx, val))
+            } else {
+                None
+            }
+        })
+    }
+
+    fn basins(&self) -> impl Iterator<Item = HashSet<(usize, usize)>> + '_ {
+        // Filled with a stack rather than recursion, as a basin can cover
+        // most of a large map
+        self.low_points().map(|(idx, _)| {
+            let mut basin = HashSet::new();
+            let mut stack = vec![idx];
+            while let Some(idx) = stack.pop() {
+                if basin.insert(idx) {
+                    stack.extend(
+                        self.neighbors(idx)
+                            .filter(|&(_, val)| val != 9)
+                            .map(|(idx, _)| idx),
+                    );
+                }
+            }
+            basin
+        })
+    }
+}
+
+impl FromStr for Map {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut width = None;
+        let mut cells = Vec::new();
+        for (idx, line) in s.lines().enumerate() {
+            let row = line
+                .chars()
+                .map(|c| {
+                    c.to_digit(10).map(|digit| digit as u8).ok_or_else(|| {
+                        Error::parse(
+                            format!("height on line {}", idx + 1),
+                            format!("{:?} is not a digit", c),
+                        )
+                    })
+                })
+                .collect::<aoc::Result<Vec<_>>>()?;
+            let expected = *width.get_or_insert(row.len());
+            if row.len() != expected {
+                return Err(Error::invalid_input(format!(
+                    "line {}: expected {} columns, found {}",
+                    idx + 1,
+                    expected,
+                    row.len()
+                )));
+            }
+            cells.extend(row);
+        }
+        match width {
+            Some(width) if width > 0 => Ok(Self { width, cells }),
+            _ => Err(Error::invalid_input("empty height map")),
+        }
+    }
+}
+
+/// A random `width` by `height` height map, split up by walls of 9 into
+/// rectangular basins no larger than `max_basin` on each side. Each basin
+/// slopes down to a single low point, as the puzzle promises.
+pub fn generate(
+    rng: &mut StdRng,
+    width: usize,
+    height: usize,
+    max_basin: usize,
+) -> String {
+    let max_basin = max_basin.max(1);
+    let mut heights = vec![vec![9_u8; width]; height];
+    let mut regions = vec![(0, 0, width, height)];
+    while let Some((x, y, w, h)) = regions.pop() {
+        if w == 0 || h == 0 {
+            continue;
+        }
+        // Cut the longer side with a wall until the basin is small enough
+        if w > max_basin || h > max_basin {
+            if w >= h {
+                let cut = rng.random_range(0..w);
+                regions.push((x, y, cut, h));
+                regions.push((x + cut + 1, y, w - cut - 1, h));
+            } else {
+                let cut = rng.random_range(0..h);
+                regions.push((x, y, w, cut));
+                regions.push((x, y + cut + 1, w, h - cut - 1));
+            }
+            continue;
+        }
+        let low = (rng.random_range(x..x + w), rng.random_range(y..y + h));
+        for (row_idx, row) in heights.iter_mut().enumerate().skip(y).take(h) {
+            for (col_idx, val) in row.iter_mut().enumerate().skip(x).take(w) {
+                let distance =
+                    col_idx.abs_diff(low.0) + row_idx.abs_diff(low.1);
+                *val = distance.min(8) as u8;
+            }
+        }
+    }
+
+    let mut output = String::with_capacity((width + 1) * height);
+    for row in heights {
+        output.extend(row.into_iter().map(|val| char::from(b'0' + val)));
+        output.push('\n');
+    }
+    output
+}
+
+pub struct Day09;
+
+impl aoc::Solution for Day09 {
+    const DAY: u8 = 9;
+    type Input = Map;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Input) -> aoc::Result<Self::Part1> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> aoc::Result<Self::Part2> {
+        part2(input)
+    }
+}
+
+/// A square map with `scale` times as many cells as the real 100 by 100
+/// one, with basins of the same size.
+impl aoc::Generate for Day09 {
+    fn generate(rng: &mut StdRng, scale: usize) -> String {
+        let side = (100 * 100 * scale).isqrt();
+        generate(rng, side, side, 12)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::{Generate, Solution};
+
+    static EXAMPLE_INPUT: &str = include_str!("../examples/example.txt");
+
+    aoc::example_tests!(Day09);
+
+    aoc::generate_tests!(Day09, [1, 10]);
+
+    #[test]
+    fn test_generate_size() {
+        for scale in [1, 10] {
+            let input = Day09::generate(&mut aoc::generate::rng(0), scale);
+            let map = Day09::parse(&input).unwrap();
+            assert_eq!(map.width(), (10_000 * scale).isqrt());
+            assert_eq!(map.height(), map.width());
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let map: Map = EXAMPLE_INPUT.parse().unwrap();
+        assert_eq!((map.width(), map.height()), (10, 5));
+        assert_eq!(map.get((0, 0)).unwrap(), 2);
+        assert_eq!(map.get((9, 0)).unwrap(), 0);
+        assert_eq!(map.get((0, 4)).unwrap(), 9);
+        assert_eq!(map.get((9, 4)).unwrap(), 8);
+        assert_eq!(map.get((0, 5)), None);
+        assert_eq!(map.get((10, 0)), None);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let map: Map = EXAMPLE_INPUT.parse().unwrap();
+        let neighbors: Vec<_> =
+            map.neighbors((1, 1)).map(|(_, v)| v).collect();
+        let expected = vec![1, 3, 8, 8];
+        assert_eq!(neighbors, expected);
+        let neighbors: Vec<_> =
+            map.neighbors((9, 4)).map(|(_, v)| v).collect();
+        let expected = vec![9, 7];
+        assert_eq!(neighbors, expected);
+    }
+
+    #[test]
+    fn test_items() {
+        let map: Map = EXAMPLE_INPUT.parse().unwrap();
+        let indices: Vec<_> = map.items().collect();
+        assert_eq!(indices[0], ((0, 0), 2));
+        assert_eq!(indices.last(), Some(&((9, 4), 8)));
+    }
+
+    #[test]
+    fn test_large_basins() {
+        let mut rng = aoc::generate::rng(0);
+        let map: Map = generate(&mut rng, 100, 100, 100).parse().unwrap();
+        assert_eq!(part2(&map).unwrap(), 100 * 100);
+
+        // Three low points in one basin make for a product too large for u32
+        let mut rows = vec![vec![b'1'; 100]; 100];
+        for idx in [0, 50, 99] {
+            rows[idx][idx] = b'0';
+        }
+        let input: Vec<_> = rows
+            .into_iter()
+            .map(|row| String::from_utf8(row).unwrap())
+            .collect();
+        let map: Map = input.join("\n").parse().unwrap();
+        assert!(part2(&map).is_err());
+    }
+
+    aoc::rejects_input!(
+        Day09,
+        ["", "\n", "2199943210\n219994321", "21999x3210"]
+    );
+
+    aoc::parse_never_panics!(Day09, "\\PC*|([0-9]{1,5}\n){0,5}");
+}
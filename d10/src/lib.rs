@@ -0,0 +1,352 @@
+use aoc::{
+    generate::{RngExt, StdRng},
+    Error,
+};
+use std::{collections::HashMap, str::FromStr};
+
+#[derive(Clone, PartialEq, Debug)]
+enum Direction {
+    Opening,
+    Closing,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum Style {
+    Round,
+    Square,
+    Curly,
+    Angled,
+}
+
+#[derive(Clone, Debug)]
+struct ChunkPoint((Direction, Style));
+
+#[derive(Debug)]
+pub struct Line(Vec<ChunkPoint>);
+
+impl FromStr for Line {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use Direction::*;
+        use Style::*;
+
+        Ok(Self(
+            s.trim()
+                .chars()
+                .map(|c| match c {
+                    '[' => Ok(ChunkPoint((Opening, Square))),
+                    ']' => Ok(ChunkPoint((Closing, Square))),
+                    '(' => Ok(ChunkPoint((Opening, Round))),
+                    ')' => Ok(ChunkPoint((Closing, Round))),
+                    '{' => Ok(ChunkPoint((Opening, Curly))),
+                    '}' => Ok(ChunkPoint((Closing, Curly))),
+                    '<' => Ok(ChunkPoint((Opening, Angled))),
+                    '>' => Ok(ChunkPoint((Closing, Angled))),
+                    c => Err(Error::parse(
+                        "line",
+                        format!("bad input char {:?}", c),
+                    )),
+                })
+                .collect::<Result<_, Self::Err>>()?,
+        ))
+    }
+}
+
+/// Why a line isn't a valid set of chunks. Corrupted lines are scored in part
+/// 1, so this is an expected outcome rather than an error.
+#[derive(Debug)]
+enum Corruption {
+    Matching {
+        expected: ChunkPoint,
+        found: ChunkPoint,
+    },
+    PrematureClosing(ChunkPoint),
+}
+
+impl Corruption {
+    /// The first illegal character on the line
+    fn found(&self) -> &ChunkPoint {
+        match self {
+            Corruption::Matching { found, .. } => found,
+            Corruption::PrematureClosing(found) => found,
+        }
+    }
+}
+
+impl std::fmt::Display for Corruption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Corruption::Matching { expected, found } => {
+                write!(f, "expected {}, found {}", expected, found)
+            }
+            Corruption::PrematureClosing(found) => {
+                write!(f, "found unexpected {}", found)
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for ChunkPoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", char::from(self))
+    }
+}
+
+impl From<&ChunkPoint> for char {
+    fn from(cp: &ChunkPoint) -> Self {
+        use Direction::*;
+        use Style::*;
+        match cp {
+            ChunkPoint((Opening, Square)) => '[',
+            ChunkPoint((Closing, Square)) => ']',
+            ChunkPoint((Opening, Round)) => '(',
+            ChunkPoint((Closing, Round)) => ')',
+            ChunkPoint((Opening, Curly)) => '{',
+            ChunkPoint((Closing, Curly)) => '}',
+            ChunkPoint((Opening, Angled)) => '<',
+            ChunkPoint((Closing, Angled)) => '>',
+        }
+    }
+}
+
+impl Line {
+    fn parse(&self) -> Result<Vec<ChunkPoint>, Corruption> {
+        let mut stack = Vec::new();
+        use Direction::*;
+        for cp in self.0.iter() {
+            match (stack.last(), cp) {
+                // Happy path: opening is always ok
+                (_, cp @ ChunkPoint((Opening, _))) => stack.push(cp.clone()),
+
+                // Happy path: matched closer
+                (
+                    Some(&ChunkPoint((Opening, ref a))),
+                    ChunkPoint((Closing, b)),
+                ) if a == b => {
+                    stack.pop();
+                }
+
+                // Stack empty but found closing
+                (None, cp @ ChunkPoint((Closing, _))) => {
+                    return Err(Corruption::PrematureClosing(cp.clone()))
+                }
+
+                // Mismatch
+                (Some(a), b) => {
+                    let expected = if let ChunkPoint((Opening, s)) = a {
+                        ChunkPoint((Closing, s.clone()))
+                    } else {
+                        unreachable!("only opening goes into the stack")
+                    };
+                    return Err(Corruption::Matching {
+                        expected,
+                        found: b.clone(),
+                    });
+                }
+            }
+        }
+        Ok(stack)
+    }
+
+    fn get_completion(
+        chunks: &[ChunkPoint],
+    ) -> impl Iterator<Item = ChunkPoint> + '_ {
+        chunks.iter().rev().map(|ChunkPoint((_, style))| {
+            ChunkPoint((Direction::Closing, style.clone()))
+        })
+    }
+}
+
+fn points_map() -> HashMap<char, u32> {
+    [(')', 3), (']', 57), ('}', 1197), ('>', 25137)].into()
+}
+
+fn points_map2() -> HashMap<char, u32> {
+    [(')', 1), (']', 2), ('}', 3), ('>', 4)].into()
+}
+
+fn part1(input: &[Line]) -> aoc::Result<u32> {
+    let map = points_map();
+    input
+        .iter()
+        .map(|line| match line.parse() {
+            Err(e) => map[&char::from(e.found())],
+            _ => 0,
+        })
+        .try_fold(0_u32, |acc, points| acc.checked_add(points))
+        .ok_or_else(|| {
+            Error::invalid_input("syntax error score overflows a u32")
+        })
+}
+
+fn part2(input: &[Line]) -> aoc::Result<u64> {
+    let map = points_map2();
+    let mut scores: Vec<_> = input
+        .iter()
+        .filter_map(|line| line.parse().ok())
+        .map(|line| {
+            Line::get_completion(&line)
+                .try_fold(0_u64, |acc, cp| {
+                    let c = char::from(&cp);
+                    let points = map[&c];
+                    acc.checked_mul(5)?.checked_add(points as u64)
+                })
+                .ok_or_else(|| {
+                    Error::invalid_input("completion score overflows a u64")
+                })
+        })
+        .collect::<aoc::Result<_>>()?;
+    scores.sort_unstable();
+    scores
+        .get(scores.len() / 2)
+        .copied()
+        .ok_or_else(|| Error::no_solution("no incomplete lines"))
+}
+
+fn parse_input(input: &str) -> aoc::Result<Vec<Line>> {
+    let lines: Vec<_> = input
+        .lines()
+        .map(|line| line.parse())
+        .collect::<Result<_, _>>()?;
+    if lines.is_empty() {
+        return Err(Error::invalid_input("no lines"));
+    }
+    Ok(lines)
+}
+/// Random lines of `length` chunk characters, about half corrupted and the
+/// rest incomplete. Chunks nest at most 20 deep so that completion scores
+/// fit in a `u64`, and there is always an odd number of incomplete lines so
+/// that part 2 has a middle score.
+pub fn generate(rng: &mut StdRng, lines: usize, length: usize) -> String {
+    const PAIRS: [(char, char); 4] =
+        [('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')];
+    let mut incomplete = 0;
+    let mut output = String::new();
+    for idx in 0..lines {
+        let mut open = Vec::new();
+        let mut line = String::new();
+        let mut closers = Vec::new();
+        while line.len() < length || open.is_empty() {
+            if !open.is_empty() && (open.len() >= 20 || rng.random_bool(0.45))
+            {
+                closers.push(line.len());
+                line.extend(open.pop());
+            } else {
+                let (opener, closer) = PAIRS[rng.random_range(0..4)];
+                open.push(closer);
+                line.push(opener);
+            }
+        }
+
+        let corrupt = if idx + 1 == lines {
+            incomplete % 2 == 1
+        } else {
+            rng.random_bool(0.5)
+        };
+        match closers.len() {
+            0 => incomplete += 1,
+            _ if !corrupt => incomplete += 1,
+            count => {
+                // Swapping any closer for another makes the line corrupted
+                let at = closers[rng.random_range(0..count)];
+                let current = char::from(line.as_bytes()[at]);
+                let wrong = PAIRS
+                    .iter()
+                    .map(|&(_, closer)| closer)
+                    .filter(|&closer| closer != current)
+                    .nth(rng.random_range(0..3))
+                    .unwrap_or(current);
+                line.replace_range(at..=at, wrong.encode_utf8(&mut [0; 4]));
+            }
+        }
+        output.push_str(&line);
+        output.push('\n');
+    }
+    output
+}
+
+pub struct Day10;
+
+impl aoc::Solution for Day10 {
+    const DAY: u8 = 10;
+    type Input = Vec<Line>;
+    type Part1 = u32;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> aoc::Result<Self::Part1> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> aoc::Result<Self::Part2> {
+        part2(input)
+    }
+}
+
+impl aoc::Generate for Day10 {
+    fn generate(rng: &mut StdRng, scale: usize) -> String {
+        generate(rng, 100 * scale, 100)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE_INPUT: &str = include_str!("../examples/example.txt");
+
+    aoc::example_tests!(Day10);
+
+    #[test]
+    fn test_part2() {
+        let input = parse_input(EXAMPLE_INPUT).unwrap();
+        assert_eq!(part2(&input[..1]).unwrap(), 288957);
+    }
+
+    #[test]
+    fn test_get_completion() {
+        let expected = "}}]])})]
+)}>]})
+}}>}>))))
+]]}}]}]}>
+])}>";
+        let completions: String = parse_input(EXAMPLE_INPUT)
+            .unwrap()
+            .iter()
+            .filter_map(|line| {
+                line.parse().ok().map(|line| {
+                    Line::get_completion(&line)
+                        .map(|cp| char::from(&cp))
+                        .collect::<String>()
+                })
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(completions, expected);
+    }
+
+    aoc::generate_tests!(Day10);
+
+    aoc::rejects_input!(
+        Day10,
+        [
+            "",
+            "[({(<(())[]>[[{[]{<()<>>\nabc",
+            "(((((((((((((((((((((((((((((((",
+        ]
+    );
+
+    #[test]
+    fn test_score_overflow() {
+        // Each `>` scores 25137, so this is just past u32::MAX
+        let input = parse_input(&">\n".repeat(170_863)).unwrap();
+        assert!(part1(&input).is_err());
+        assert_eq!(part1(&input[1..]).unwrap(), 170_862 * 25137);
+    }
+
+    aoc::parse_never_panics!(Day10, "\\PC*|([\\[\\](){}<>]{0,40}\n){0,5}");
+}
@@ -0,0 +1,391 @@
+#![warn(clippy::pedantic)]
+use std::str::FromStr;
+use std::{collections::HashSet, fmt};
+
+use aoc::generate::{RngExt, StdRng};
+
+#[derive(Clone, Eq, Hash, PartialEq)]
+enum Octopus {
+    Flashed,
+    Unflashed(u8),
+}
+
+#[derive(Clone, Eq, Hash, PartialEq)]
+pub struct Octopi(Vec<Vec<Octopus>>);
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct Position {
+    x: usize,
+    y: usize,
+}
+
+impl Octopi {
+    fn enumerate_mut(
+        &mut self,
+    ) -> impl Iterator<Item = (Position, &mut Octopus)> {
+        self.0.iter_mut().enumerate().flat_map(|(y, row)| {
+            row.iter_mut()
+                .enumerate()
+                .map(move |(x, oct)| (Position { x, y }, oct))
+        })
+    }
+
+    fn reset_flashed(&mut self) {
+        for row in &mut self.0 {
+            for oct in row {
+                if let Octopus::Flashed = oct {
+                    *oct = Octopus::Unflashed(0);
+                }
+            }
+        }
+    }
+
+    fn neighbor_positions(
+        target: &Position,
+        (x_max, y_max): (usize, usize),
+    ) -> impl Iterator<Item = Position> {
+        const ADJ: [(usize, usize); 3] = [(0, 1), (1, 0), (1, 1)];
+        let Position { x, y } = target;
+        let hs: HashSet<Position> = ADJ
+            .iter()
+            .flat_map(move |(dx, dy)| {
+                [
+                    (x.checked_add(*dx), y.checked_add(*dy)),
+                    (x.checked_sub(*dx), y.checked_sub(*dy)),
+                    (x.checked_add(*dx), y.checked_sub(*dy)),
+                    (x.checked_sub(*dx), y.checked_add(*dy)),
+                ]
+            })
+            .filter_map(|idx| {
+                if let (Some(x), Some(y)) = idx {
+                    if x < x_max && y < y_max {
+                        return Some(Position { x, y });
+                    }
+                }
+                None
+            })
+            .collect();
+        hs.into_iter()
+    }
+
+    fn flash_count(&mut self) -> u32 {
+        let mut sum = 0;
+        let (width, height) = (self.0[0].len(), self.0.len());
+
+        // Initialize to all octopi in order to start by incrementing everything
+        let mut neighbor_positions: Vec<_> = (0..(self.0.len()))
+            .flat_map(|y| {
+                (0..(self.0[0].len())).map(move |x| Position { x, y })
+            })
+            .collect();
+
+        loop {
+            let mut new_flashes = 0;
+
+            // increment all unflashed neighbors
+            while let Some(pos) = neighbor_positions.pop() {
+                if let Some(Octopus::Unflashed(ref mut v)) =
+                    self.0.get_mut(pos.y).and_then(|row| row.get_mut(pos.x))
+                {
+                    *v += 1;
+                }
+            }
+
+            for (pos, oct) in self.enumerate_mut() {
+                match oct {
+                    Octopus::Unflashed(v) if *v >= 10 => {
+                        new_flashes += 1;
+
+                        *oct = Octopus::Flashed;
+                        neighbor_positions.extend(Octopi::neighbor_positions(
+                            &pos,
+                            (width, height),
+                        ));
+                    }
+                    _ => (),
+                }
+            }
+
+            if new_flashes == 0 {
+                break;
+            }
+            sum += new_flashes;
+        }
+
+        self.reset_flashed();
+        sum
+    }
+}
+
+impl fmt::Display for Octopi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.0
+                .iter()
+                .map(|row| row
+                    .iter()
+                    .map(|oct| {
+                        if let &Octopus::Unflashed(v) = oct {
+                            v.to_string()
+                        } else {
+                            "F".to_string()
+                        }
+                    })
+                    .collect::<String>())
+                .collect::<Vec<_>>()
+                .join("\n")
+        )
+    }
+}
+
+impl FromStr for Octopi {
+    type Err = aoc::Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let rows: Vec<Vec<Octopus>> = input
+            .lines()
+            .map(|line| {
+                line.chars()
+                    .map(|c| {
+                        let energy = c.to_digit(10).ok_or_else(|| {
+                            aoc::Error::parse(
+                                "octopus",
+                                format!("{c:?} is not a digit"),
+                            )
+                        })?;
+                        Ok(Octopus::Unflashed(energy.try_into()?))
+                    })
+                    .collect()
+            })
+            .collect::<aoc::Result<_>>()?;
+
+        // `flash_count` and `part2` rely on a non-empty, rectangular grid
+        let width = rows.first().map_or(0, Vec::len);
+        if width == 0 {
+            return Err(aoc::Error::invalid_input("no octopi"));
+        }
+        if let Some((y, row)) =
+            rows.iter().enumerate().find(|(_, row)| row.len() != width)
+        {
+            return Err(aoc::Error::invalid_input(format!(
+                "row {} has {} octopi, expected {}",
+                y + 1,
+                row.len(),
+                width
+            )));
+        }
+        Ok(Octopi(rows))
+    }
+}
+
+fn part1(octopi: &mut Octopi, days: usize) -> u32 {
+    (0..days).map(|_| octopi.flash_count()).sum()
+}
+
+fn part2(octopi: &mut Octopi) -> aoc::Result<u32> {
+    let mut seen = HashSet::new();
+    let mut counter = 0;
+    while !octopi.0.iter().flat_map(|row| row.iter()).all(|oct| {
+        matches!(
+            (&octopi.0[0][0], oct),
+            (Octopus::Unflashed(first), Octopus::Unflashed(second))
+                if first == second
+        )
+    }) {
+        // There are only so many states, so the octopi either synchronize
+        // or end up repeating themselves
+        if !seen.insert(octopi.clone()) {
+            return Err(aoc::Error::no_solution(format!(
+                "octopi never all flash at once, repeating after step \
+                 {counter}"
+            )));
+        }
+        octopi.flash_count();
+        counter += 1;
+    }
+    Ok(counter)
+}
+
+/// A random `width` by `height` grid of octopus energy levels.
+#[must_use]
+pub fn generate(rng: &mut StdRng, width: usize, height: usize) -> String {
+    let mut output = String::with_capacity((width + 1) * height);
+    for _ in 0..height {
+        output.extend(
+            (0..width).map(|_| char::from(b'0' + rng.random_range(0..10))),
+        );
+        output.push('\n');
+    }
+    output
+}
+
+pub struct Day11;
+
+impl aoc::Solution for Day11 {
+    const DAY: u8 = 11;
+    type Input = Octopi;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Input) -> aoc::Result<Self::Part1> {
+        Ok(part1(&mut input.clone(), 100))
+    }
+
+    fn part2(input: &Self::Input) -> aoc::Result<Self::Part2> {
+        part2(&mut input.clone())
+    }
+}
+
+/// Random grids rarely synchronize, and larger ones almost never do, so this
+/// ignores `scale` and keeps trying 10 by 10 grids until part 2 has an
+/// answer. Use [generate] for larger grids.
+impl aoc::Generate for Day11 {
+    fn generate(rng: &mut StdRng, _scale: usize) -> String {
+        loop {
+            let input = generate(rng, 10, 10);
+            if let Ok(mut octopi) = input.parse() {
+                if part2(&mut octopi).is_ok() {
+                    return input;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::Solution;
+
+    static EXAMPLE_INPUT: &str = include_str!("../examples/example.txt");
+
+    aoc::example_tests!(Day11);
+
+    aoc::generate_tests!(Day11);
+
+    #[test]
+    fn test_steps() {
+        let small_example: &str = "11111
+19991
+19191
+19991
+11111";
+
+        let mut octopi: Octopi = small_example.parse().unwrap();
+        let steps = [
+            "34543
+40004
+50005
+40004
+34543",
+            "45654
+51115
+61116
+51115
+45654",
+        ];
+
+        for step in steps {
+            let _ = octopi.flash_count();
+            assert_eq!(octopi.to_string(), step);
+        }
+    }
+
+    #[test]
+    fn debug_steps() {
+        let tiny_example: &str = "0000
+0800
+9000";
+
+        let mut octopi: Octopi = tiny_example.parse().unwrap();
+        let steps = [
+            "2221
+3021
+0321",
+            "3332
+4132
+1432",
+            "4443
+5243
+2543",
+        ];
+
+        for expected in steps {
+            let _ = octopi.flash_count();
+            println!("{octopi}\n\n{expected}");
+            assert_eq!(octopi.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_neighbor_positions() {
+        let tests = [
+            (Position { x: 3, y: 9 }, (20, 20)),
+            (Position { x: 0, y: 0 }, (5, 5)),
+            (Position { x: 5, y: 10 }, (6, 11)),
+            (Position { x: 5, y: 5 }, (6, 11)),
+        ];
+        let mut expected = [
+            vec![
+                (2_usize, 9_usize),
+                (4, 9),
+                (3, 8),
+                (3, 10),
+                (2, 8),
+                (4, 10),
+                (4, 8),
+                (2, 10),
+            ],
+            vec![(0, 1), (1, 0), (1, 1)],
+            vec![(5, 9), (4, 10), (4, 9)],
+            vec![(5, 4), (5, 6), (4, 5), (4, 4), (4, 6)],
+        ];
+
+        assert_eq!(tests.len(), expected.len());
+
+        for ((start, (xmax, ymax)), output) in
+            tests.iter().zip(expected.iter_mut())
+        {
+            let mut pos: Vec<_> =
+                Octopi::neighbor_positions(start, (*xmax, *ymax))
+                    .map(|p| (p.x, p.y))
+                    .collect();
+            pos.sort_unstable();
+            output.sort_unstable();
+
+            assert_eq!(&pos, output);
+        }
+    }
+
+    #[test]
+    fn test_fmt() {
+        let octopi: Octopi = EXAMPLE_INPUT.parse().unwrap();
+        let output = octopi.to_string();
+        assert_eq!(output, EXAMPLE_INPUT.trim_end());
+    }
+
+    #[test]
+    fn test_never_synchronized() {
+        let mut octopi: Octopi = "09".parse().unwrap();
+        assert!(part2(&mut octopi).is_err());
+    }
+
+    aoc::rejects_input!(Day11, ["", "\n", "123\n45", "12a", "1\u{0663}"]);
+
+    proptest::proptest! {
+        #[test]
+        fn test_parse_never_panics(
+            s in "\\PC*|([0-9]{1,5}\n){0,5}",
+        ) {
+            if let Ok(mut octopi) = Day11::parse(&s) {
+                let _ = part1(&mut octopi, 10);
+            }
+        }
+    }
+}
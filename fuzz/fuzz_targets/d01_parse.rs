@@ -0,0 +1,11 @@
+#![no_main]
+
+use aoc::Solution;
+use d01::Day01;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(s) = std::str::from_utf8(data) {
+        let _ = Day01::parse(s);
+    }
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use d03::{Report, ReportNumber};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(s) = std::str::from_utf8(data) {
+        let _ = s.parse::<ReportNumber>();
+        let _ = s.parse::<Report>();
+    }
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use aoc::Solution;
+use d07::Day07;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(s) = std::str::from_utf8(data) {
+        let _ = Day07::parse(s);
+    }
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+use aoc::Solution;
+use d08::Day08;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(s) = std::str::from_utf8(data) {
+        // The displays are only decoded in part 2
+        if let Ok(input) = Day08::parse(s) {
+            let _ = Day08::part2(&input);
+        }
+    }
+});